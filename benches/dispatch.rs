@@ -0,0 +1,93 @@
+use std::hint::black_box;
+use std::time::Instant;
+
+use cursor_iter::{Cursor, Dispatch};
+
+#[derive(Clone, Copy)]
+enum Tok {
+    Ident,
+    Number,
+    Punct,
+    Space,
+    Unknown,
+}
+
+fn run(cursor: &mut Cursor, pred: fn(char) -> bool, tok: Tok) -> Tok {
+    while cursor.peek_char().is_some_and(pred) {
+        cursor.next();
+    }
+    tok
+}
+
+fn ident(cursor: &mut Cursor) -> Tok {
+    run(cursor, |c| c.is_alphanumeric() || c == '_', Tok::Ident)
+}
+
+fn number(cursor: &mut Cursor) -> Tok {
+    run(cursor, |c| c.is_ascii_digit(), Tok::Number)
+}
+
+fn space(cursor: &mut Cursor) -> Tok {
+    run(cursor, char::is_whitespace, Tok::Space)
+}
+
+fn punct(cursor: &mut Cursor) -> Tok {
+    cursor.next();
+    Tok::Punct
+}
+
+fn unknown(cursor: &mut Cursor) -> Tok {
+    cursor.next();
+    Tok::Unknown
+}
+
+fn naive(cursor: &mut Cursor) -> Option<Tok> {
+    let c = cursor.peek_char()?;
+    let tok = if c.is_ascii_alphabetic() || c == '_' {
+        ident(cursor)
+    } else if c.is_ascii_digit() {
+        number(cursor)
+    } else if "+-*/(){};,.<>=!&|".contains(c) {
+        punct(cursor)
+    } else if c.is_whitespace() {
+        space(cursor)
+    } else if c.is_alphabetic() {
+        ident(cursor)
+    } else {
+        unknown(cursor)
+    };
+    Some(tok)
+}
+
+fn time(name: &str, mut f: impl FnMut() -> usize) {
+    let start = Instant::now();
+    let iters = 10;
+    for _ in 0..iters {
+        black_box(f());
+    }
+    println!("{name:<24} {:>10.2?}/iter", start.elapsed() / iters);
+}
+
+fn main() {
+    let data =
+        "fn main() { let x_1 = foo(42, bar) * 7; if a <= b && !c { ünï(); } }\n".repeat(20_000);
+    let table = Dispatch::builder(unknown)
+        .range('a'..='z', ident)
+        .range('A'..='Z', ident)
+        .char('_', ident)
+        .range('0'..='9', number)
+        .chars("+-*/(){};,.<>=!&|", punct)
+        .class(char::is_whitespace, space)
+        .class(|c| !c.is_ascii() && c.is_alphabetic(), ident)
+        .build()
+        .unwrap();
+
+    time("dispatch table", || {
+        let mut cursor = Cursor::new(&data);
+        std::iter::from_fn(|| table.dispatch(&mut cursor)).count()
+    });
+    time("if/else chain", || {
+        let mut cursor = Cursor::new(&data);
+        std::iter::from_fn(|| naive(&mut cursor)).count()
+    });
+}
@@ -0,0 +1,34 @@
+use std::hint::black_box;
+use std::time::Instant;
+
+use cursor_iter::Cursor;
+
+/// The char-window scan that `take_until` would otherwise need.
+fn naive_find(haystack: &str, needle: &str) -> Option<usize> {
+    haystack
+        .char_indices()
+        .map(|(i, _)| i)
+        .find(|&i| haystack[i..].starts_with(needle))
+}
+
+fn time<T>(name: &str, mut f: impl FnMut() -> T) {
+    let start = Instant::now();
+    let iters = 5;
+    for _ in 0..iters {
+        black_box(f());
+    }
+    println!("{name:<24} {:>10.2?}/iter", start.elapsed() / iters);
+}
+
+fn main() {
+    let mut data = "lorem ipsum dolor sit amet, ünïcödé 👋\n".repeat(100_000);
+    data.push_str("NEEDLE");
+
+    time("take_until (memmem)", || {
+        let mut cursor = Cursor::new(&data);
+        cursor.take_until("NEEDLE").map(|(_, s)| s.len())
+    });
+    time("take_until (naive)", || naive_find(&data, "NEEDLE"));
+    time("find_all", || Cursor::new(&data).find_all("amet").count());
+    time("split_str", || Cursor::new(&data).split_str(", ").count());
+}
@@ -0,0 +1,96 @@
+use std::fmt;
+
+use crate::{Cursor, Position, Span};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BalanceError {
+    /// The cursor was not at the opening delimiter.
+    NotAtOpen { position: Position },
+    /// The input ended while the delimiter at `open` was still open.
+    Unclosed { open: Position },
+}
+
+impl fmt::Display for BalanceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotAtOpen { position } => {
+                write!(f, "expected an opening delimiter at {position}")
+            }
+            Self::Unclosed { open } => write!(f, "unclosed delimiter at {open}"),
+        }
+    }
+}
+
+impl std::error::Error for BalanceError {}
+
+impl<'a> Cursor<'a> {
+    /// Consumes from the `open` delimiter at the cursor through its matching
+    /// `close`, counting nested pairs, and returns the span and text of the
+    /// whole block. Delimiters inside strings or comments are counted too.
+    /// On error the cursor does not move; an unclosed block reports the
+    /// innermost delimiter left open.
+    pub fn consume_balanced(
+        &mut self,
+        open: char,
+        close: char,
+    ) -> Result<(Span, &'a str), BalanceError> {
+        debug_assert_ne!(open, close, "use next_quoted for matching pairs");
+        let start = self.checkpoint();
+        if self.peek_char() != Some(open) {
+            return Err(BalanceError::NotAtOpen {
+                position: Cursor::position(self),
+            });
+        }
+        let mut opened = Vec::new();
+        loop {
+            let position = Cursor::position(self);
+            match self.next() {
+                Some((_, c)) if c == open => opened.push(position),
+                Some((_, c)) if c == close => {
+                    opened.pop();
+                    if opened.is_empty() {
+                        let span = self.span_since(&start);
+                        return Ok((span, self.slice(span)));
+                    }
+                }
+                Some(_) => {}
+                None => {
+                    self.restore(start);
+                    let open = opened.pop().expect("an open delimiter");
+                    return Err(BalanceError::Unclosed { open });
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_consume_balanced() {
+        let mut cursor = Cursor::new("{a {b} {c {}}} rest");
+        let (span, block) = cursor.consume_balanced('{', '}').unwrap();
+        assert_eq!((span, block), (Span::new(0, 14), "{a {b} {c {}}}"));
+        assert_eq!(cursor.peek(), Some((14, ' ')));
+        assert_eq!(
+            cursor.consume_balanced('{', '}'),
+            Err(BalanceError::NotAtOpen {
+                position: Cursor::position(&cursor)
+            })
+        );
+    }
+
+    #[test]
+    fn test_unclosed() {
+        let mut cursor = Cursor::new("(a (b)\n  (c");
+        let err = cursor.consume_balanced('(', ')').unwrap_err();
+        let BalanceError::Unclosed { open } = err else {
+            panic!("{err:?}");
+        };
+        assert_eq!((open.byte, open.line, open.column), (9, 1, 2));
+        assert_eq!(err.to_string(), "unclosed delimiter at 2:3");
+        assert!(cursor.is_at_start());
+    }
+}
@@ -0,0 +1,104 @@
+use crate::Cursor;
+
+#[cfg(feature = "casefold")]
+fn fold(c: char) -> impl Iterator<Item = char> {
+    caseless::Caseless::default_case_fold(std::iter::once(c))
+}
+
+#[cfg(not(feature = "casefold"))]
+fn fold(c: char) -> impl Iterator<Item = char> {
+    std::iter::once(c.to_ascii_lowercase())
+}
+
+/// Byte length of the prefix of `haystack` that equals `needle` ignoring
+/// case. The lengths can differ under full case folding, where `ß` matches
+/// `SS`.
+fn prefix_len_ignore_case(haystack: &str, needle: &str) -> Option<usize> {
+    let mut wanted = needle.chars().flat_map(fold).peekable();
+    for (i, c) in haystack.char_indices() {
+        if wanted.peek().is_none() {
+            return Some(i);
+        }
+        for folded in fold(c) {
+            if wanted.next() != Some(folded) {
+                return None;
+            }
+        }
+    }
+    wanted.peek().is_none().then_some(haystack.len())
+}
+
+impl<'a> Cursor<'a> {
+    /// Like [`Cursor::starts_with`] for a `&str`, ignoring case. Only ASCII
+    /// letters fold unless the `casefold` feature enables full Unicode case
+    /// folding.
+    pub fn starts_with_ignore_case(&self, s: &str) -> bool {
+        prefix_len_ignore_case(self.forward(), s).is_some()
+    }
+
+    /// Consumes `s` if the remaining input starts with it ignoring case, as
+    /// in [`Cursor::starts_with_ignore_case`], and returns the consumed text.
+    pub fn eat_str_ignore_case(&mut self, s: &str) -> Option<&'a str> {
+        let len = prefix_len_ignore_case(self.forward(), s)?;
+        let start = self.offset;
+        self.advance_to(start + len);
+        Some(&self.data[start..self.offset])
+    }
+
+    /// [`Cursor::consume_longest`] ignoring case, for keyword tables of
+    /// case-insensitive languages such as SQL. The longest match is the one
+    /// covering the most input.
+    pub fn consume_longest_ignore_case(&mut self, literals: &[&str]) -> Option<(usize, &'a str)> {
+        let rest = self.forward();
+        let len = literals
+            .iter()
+            .filter_map(|literal| prefix_len_ignore_case(rest, literal))
+            .max()?;
+        let start = self.offset;
+        self.advance_to(start + len);
+        Some((start, &rest[..len]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ignore_case() {
+        let mut cursor = Cursor::new("SeLeCt * FROM t");
+        assert!(cursor.starts_with_ignore_case("select"));
+        assert!(!cursor.starts_with_ignore_case("selected"));
+        assert!(cursor.starts_with_ignore_case(""));
+        assert_eq!(cursor.eat_str_ignore_case("SELECT"), Some("SeLeCt"));
+        assert_eq!(cursor.eat_str_ignore_case("from"), None);
+        assert_eq!(cursor.offset(), 6);
+
+        const KEYWORDS: &[&str] = &["from", "for", "fromage"];
+        cursor.advance_by(3);
+        assert_eq!(
+            cursor.consume_longest_ignore_case(KEYWORDS),
+            Some((9, "FROM"))
+        );
+        assert_eq!(cursor.consume_longest_ignore_case(KEYWORDS), None);
+    }
+
+    #[cfg(not(feature = "casefold"))]
+    #[test]
+    fn test_ascii_folding() {
+        assert!(!Cursor::new("ÉTÉ").starts_with_ignore_case("été"));
+        assert!(Cursor::new("éTé").starts_with_ignore_case("été"));
+    }
+
+    #[cfg(feature = "casefold")]
+    #[test]
+    fn test_unicode_folding() {
+        assert!(Cursor::new("ÉTÉ").starts_with_ignore_case("été"));
+        let mut cursor = Cursor::new("STRASSE 5");
+        assert_eq!(cursor.eat_str_ignore_case("straße"), Some("STRASSE"));
+        let mut cursor = Cursor::new("Straße");
+        // `ß` folds to `ss`, so a needle can't end halfway through it.
+        assert_eq!(cursor.eat_str_ignore_case("STRAS"), None);
+        assert_eq!(cursor.eat_str_ignore_case("strasse"), Some("Straße"));
+    }
+}
@@ -0,0 +1,209 @@
+use std::borrow::Cow;
+use std::ops::{Deref, DerefMut};
+
+use crate::{Cursor, Position};
+
+/// A saved cursor position to roll back to with [`Cursor::restore`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checkpoint {
+    offset: usize,
+    line: usize,
+    column: usize,
+    char_idx: usize,
+}
+
+impl Checkpoint {
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The checkpoint of a cursor at `position`.
+    pub(crate) const fn at(position: Position) -> Self {
+        Self {
+            offset: position.byte,
+            line: position.line,
+            column: position.column,
+            char_idx: position.char_idx,
+        }
+    }
+}
+
+impl Cursor<'_> {
+    pub const fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            offset: self.offset,
+            line: self.line,
+            column: self.column,
+            char_idx: self.char_idx,
+        }
+    }
+
+    /// Moves the cursor back (or forward) to `checkpoint`, which must have
+    /// been taken from a cursor over the same input.
+    pub fn restore(&mut self, checkpoint: Checkpoint) {
+        debug_assert!(self.data.is_char_boundary(checkpoint.offset));
+        self.offset = checkpoint.offset;
+        self.line = checkpoint.line;
+        self.column = checkpoint.column;
+        self.char_idx = checkpoint.char_idx;
+    }
+}
+
+/// A speculative parse started by [`Cursor::transaction`]. Derefs to the
+/// cursor; unless [`Transaction::commit`] is called, dropping it moves the
+/// cursor back to where the transaction started, so early returns and `?`
+/// roll back too.
+pub struct Transaction<'a, 'b> {
+    cursor: &'b mut Cursor<'a>,
+    start: Checkpoint,
+    committed: bool,
+}
+
+impl Transaction<'_, '_> {
+    /// Keeps the cursor where it is.
+    pub fn commit(mut self) {
+        self.committed = true;
+    }
+
+    pub const fn start(&self) -> Checkpoint {
+        self.start
+    }
+}
+
+impl<'a> Deref for Transaction<'a, '_> {
+    type Target = Cursor<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        self.cursor
+    }
+}
+
+impl DerefMut for Transaction<'_, '_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.cursor
+    }
+}
+
+impl Drop for Transaction<'_, '_> {
+    fn drop(&mut self) {
+        if !self.committed {
+            self.cursor.restore(self.start);
+        }
+    }
+}
+
+impl<'a> Cursor<'a> {
+    pub fn transaction(&mut self) -> Transaction<'a, '_> {
+        Transaction {
+            start: self.checkpoint(),
+            cursor: self,
+            committed: false,
+        }
+    }
+}
+
+/// Named marks, for keeping several landmarks at once. Setting a mark that
+/// already exists moves it.
+impl<'a> Cursor<'a> {
+    pub fn set_mark(&mut self, name: impl Into<Cow<'static, str>>) {
+        let name = name.into();
+        let here = self.checkpoint();
+        match self.marks.iter_mut().find(|(mark, _)| *mark == name) {
+            Some((_, checkpoint)) => *checkpoint = here,
+            None => self.marks.push((name, here)),
+        }
+    }
+
+    pub fn mark(&self, name: &str) -> Option<Checkpoint> {
+        self.marks
+            .iter()
+            .find(|(mark, _)| mark == name)
+            .map(|&(_, checkpoint)| checkpoint)
+    }
+
+    pub fn remove_mark(&mut self, name: &str) -> Option<Checkpoint> {
+        let idx = self.marks.iter().position(|(mark, _)| mark == name)?;
+        Some(self.marks.swap_remove(idx).1)
+    }
+
+    /// Moves the cursor to the mark, returning whether it exists.
+    pub fn goto_mark(&mut self, name: &str) -> bool {
+        let mark = self.mark(name);
+        if let Some(checkpoint) = mark {
+            self.restore(checkpoint);
+        }
+        mark.is_some()
+    }
+
+    /// The text between the mark and the cursor, whichever comes first.
+    pub fn slice_from_mark(&self, name: &str) -> Option<&'a str> {
+        let mark = self.mark(name)?.offset;
+        let (start, end) = (mark.min(self.offset), mark.max(self.offset));
+        Some(&self.data[start..end])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkpoint_restore() {
+        let mut cursor = Cursor::new("let x\n= 1");
+        cursor.next_word();
+        let saved = cursor.checkpoint();
+        cursor.take_until("1");
+        assert_eq!(cursor.line(), 1);
+
+        cursor.restore(saved);
+        assert_eq!(cursor.checkpoint(), saved);
+        assert_eq!((cursor.line(), cursor.column()), (0, 3));
+        assert_eq!(cursor.next_word(), Some((4, "x")));
+    }
+
+    #[test]
+    fn test_marks() {
+        let mut cursor = Cursor::new("key = value;");
+        cursor.set_mark("token");
+        cursor.next_word();
+        cursor.set_mark("last good");
+        assert_eq!(cursor.slice_from_mark("token"), Some("key"));
+
+        cursor.take_until(";");
+        assert_eq!(cursor.slice_from_mark("last good"), Some(" = value"));
+        assert!(cursor.goto_mark("token"));
+        assert_eq!(cursor.slice_from_mark("last good"), Some("key"));
+
+        cursor.set_mark("token");
+        cursor.next_word();
+        assert_eq!(cursor.slice_from_mark("token"), Some("key"));
+        assert!(cursor.remove_mark("token").is_some());
+        assert!(!cursor.goto_mark("token"));
+        assert_eq!(cursor.slice_from_mark("missing"), None);
+    }
+
+    fn assignment<'a>(cursor: &mut Cursor<'a>) -> Option<&'a str> {
+        let mut tx = cursor.transaction();
+        let (_, name) = tx.next_word()?;
+        if tx.next_word()?.1 != "=" {
+            return None;
+        }
+        tx.commit();
+        Some(name)
+    }
+
+    #[test]
+    fn test_transaction_rolls_back_unless_committed() {
+        let mut cursor = Cursor::new("x + 1");
+        assert_eq!(assignment(&mut cursor), None);
+        assert_eq!(cursor.offset(), 0);
+
+        let mut cursor = Cursor::new("y = 2");
+        assert_eq!(assignment(&mut cursor), Some("y"));
+        assert_eq!(cursor.offset(), 3);
+
+        let mut cursor = Cursor::new("z");
+        assert_eq!(assignment(&mut cursor), None);
+        assert_eq!(cursor.offset(), 0);
+    }
+}
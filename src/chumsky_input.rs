@@ -0,0 +1,206 @@
+use std::ops::{Range, RangeFrom};
+
+use chumsky::input::{ExactSizeInput, Input, SliceInput, ValueInput};
+
+use crate::{Checkpoint, Cursor, Position, Span};
+
+impl chumsky::span::Span for Span {
+    type Context = ();
+    type Offset = usize;
+
+    fn new((): (), range: Range<usize>) -> Self {
+        range.into()
+    }
+
+    fn context(&self) {}
+
+    fn start(&self) -> usize {
+        self.start
+    }
+
+    fn end(&self) -> usize {
+        self.end
+    }
+}
+
+/// Parses the input after the cursor, with byte [`Span`]s into the whole
+/// input. Parsers move a [`Checkpoint`] of the cursor through the input, so
+/// lines and columns are tracked along the way; see
+/// [`Cursor::line_col_spans`] to get them in spans.
+impl<'a> Input<'a> for Cursor<'a> {
+    type Span = Span;
+    type Token = char;
+    type MaybeToken = char;
+    type Cursor = Checkpoint;
+    type Cache = Cursor<'a>;
+
+    fn begin(self) -> (Self::Cursor, Self::Cache) {
+        (self.checkpoint(), self)
+    }
+
+    fn cursor_location(cursor: &Self::Cursor) -> usize {
+        cursor.offset()
+    }
+
+    unsafe fn next_maybe(
+        cache: &mut Self::Cache,
+        cursor: &mut Self::Cursor,
+    ) -> Option<Self::MaybeToken> {
+        cache.restore(*cursor);
+        let (_, c) = Iterator::next(cache)?;
+        *cursor = cache.checkpoint();
+        Some(c)
+    }
+
+    unsafe fn span(_cache: &mut Self::Cache, range: Range<&Self::Cursor>) -> Self::Span {
+        Span::new(range.start.offset(), range.end.offset())
+    }
+}
+
+impl<'a> ExactSizeInput<'a> for Cursor<'a> {
+    unsafe fn span_from(cache: &mut Self::Cache, range: RangeFrom<&Self::Cursor>) -> Self::Span {
+        Span::new(range.start.offset(), cache.data.len())
+    }
+}
+
+impl<'a> ValueInput<'a> for Cursor<'a> {
+    unsafe fn next(cache: &mut Self::Cache, cursor: &mut Self::Cursor) -> Option<Self::Token> {
+        Self::next_maybe(cache, cursor)
+    }
+}
+
+impl<'a> SliceInput<'a> for Cursor<'a> {
+    type Slice = &'a str;
+
+    fn full_slice(cache: &mut Self::Cache) -> Self::Slice {
+        cache.data
+    }
+
+    unsafe fn slice(cache: &mut Self::Cache, range: Range<&Self::Cursor>) -> Self::Slice {
+        &cache.data[range.start.offset()..range.end.offset()]
+    }
+
+    unsafe fn slice_from(cache: &mut Self::Cache, from: RangeFrom<&Self::Cursor>) -> Self::Slice {
+        &cache.data[from.start.offset()..]
+    }
+}
+
+/// A cursor as `chumsky` input with spans of [`Position`]s; see
+/// [`Cursor::line_col_spans`].
+#[derive(Debug, Clone)]
+pub struct LineColInput<'a>(Cursor<'a>);
+
+impl<'a> Cursor<'a> {
+    /// Wraps the cursor as `chumsky` input whose spans are ranges of
+    /// [`Position`]s, so errors can report lines and columns directly.
+    pub fn line_col_spans(self) -> LineColInput<'a> {
+        LineColInput(self)
+    }
+}
+
+fn position_at(cache: &mut Cursor<'_>, at: &Checkpoint) -> Position {
+    cache.restore(*at);
+    Cursor::position(cache)
+}
+
+impl<'a> Input<'a> for LineColInput<'a> {
+    type Span = Range<Position>;
+    type Token = char;
+    type MaybeToken = char;
+    type Cursor = Checkpoint;
+    type Cache = Cursor<'a>;
+
+    fn begin(self) -> (Self::Cursor, Self::Cache) {
+        self.0.begin()
+    }
+
+    fn cursor_location(cursor: &Self::Cursor) -> usize {
+        cursor.offset()
+    }
+
+    unsafe fn next_maybe(
+        cache: &mut Self::Cache,
+        cursor: &mut Self::Cursor,
+    ) -> Option<Self::MaybeToken> {
+        Cursor::next_maybe(cache, cursor)
+    }
+
+    unsafe fn span(cache: &mut Self::Cache, range: Range<&Self::Cursor>) -> Self::Span {
+        position_at(cache, range.start)..position_at(cache, range.end)
+    }
+}
+
+impl<'a> ExactSizeInput<'a> for LineColInput<'a> {
+    unsafe fn span_from(cache: &mut Self::Cache, range: RangeFrom<&Self::Cursor>) -> Self::Span {
+        let start = position_at(cache, range.start);
+        cache.advance_to(cache.data.len());
+        start..Cursor::position(cache)
+    }
+}
+
+impl<'a> ValueInput<'a> for LineColInput<'a> {
+    unsafe fn next(cache: &mut Self::Cache, cursor: &mut Self::Cursor) -> Option<Self::Token> {
+        Cursor::next_maybe(cache, cursor)
+    }
+}
+
+impl<'a> SliceInput<'a> for LineColInput<'a> {
+    type Slice = &'a str;
+
+    fn full_slice(cache: &mut Self::Cache) -> Self::Slice {
+        Cursor::full_slice(cache)
+    }
+
+    unsafe fn slice(cache: &mut Self::Cache, range: Range<&Self::Cursor>) -> Self::Slice {
+        <Cursor as SliceInput>::slice(cache, range)
+    }
+
+    unsafe fn slice_from(cache: &mut Self::Cache, from: RangeFrom<&Self::Cursor>) -> Self::Slice {
+        Cursor::slice_from(cache, from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chumsky::error::Rich;
+    use chumsky::extra::Err;
+    use chumsky::prelude::*;
+
+    use super::*;
+
+    fn pair<'a, I>() -> impl Parser<'a, I, (&'a str, &'a str), Err<Rich<'a, char, I::Span>>>
+    where
+        I: SliceInput<'a, Token = char, Slice = &'a str> + ValueInput<'a>,
+    {
+        let word = any()
+            .filter(|c: &char| c.is_alphanumeric())
+            .repeated()
+            .at_least(1)
+            .to_slice();
+        let padding = any().filter(|c: &char| c.is_whitespace()).repeated();
+        word.then_ignore(just('=').padded_by(padding))
+            .then(word)
+            .padded_by(padding)
+    }
+
+    #[test]
+    fn test_byte_spans() {
+        let mut cursor = Cursor::new("skip: key = v1");
+        cursor.advance_by(5);
+        assert_eq!(pair().parse(cursor).into_result(), Ok(("key", "v1")));
+
+        let errors = pair().parse(Cursor::new("a =\n  ?")).into_errors();
+        assert_eq!(*errors[0].span(), Span::new(6, 7));
+    }
+
+    #[test]
+    fn test_line_col_spans() {
+        let input = Cursor::new("a =\n  ?").line_col_spans();
+        let errors = pair().parse(input).into_errors();
+        let span = errors[0].span();
+        assert_eq!(
+            (span.start.to_string(), span.end.to_string()),
+            ("2:3".into(), "2:4".into())
+        );
+    }
+}
@@ -0,0 +1,101 @@
+use crate::Cursor;
+
+/// What [`Cursor::column`] counts, set with [`Cursor::with_column_unit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ColumnUnit {
+    /// Chars. The default.
+    #[default]
+    Char,
+    /// UTF-8 bytes.
+    Byte,
+    /// UTF-16 code units, the way LSP and many editors count columns.
+    Utf16,
+}
+
+impl ColumnUnit {
+    /// How many columns `c` takes up.
+    pub(crate) fn width_of(self, c: char) -> usize {
+        match self {
+            Self::Char => 1,
+            Self::Byte => c.len_utf8(),
+            Self::Utf16 => c.len_utf16(),
+        }
+    }
+
+    /// How many columns `text` takes up.
+    pub(crate) fn width(self, text: &str) -> usize {
+        match self {
+            Self::Char => text.chars().count(),
+            Self::Byte => text.len(),
+            Self::Utf16 => text.chars().map(char::len_utf16).sum(),
+        }
+    }
+}
+
+impl<'a> Cursor<'a> {
+    /// Counts columns in `unit`, recomputing the cursor's column for it.
+    /// Seeking by line and column and [`Cursor::position_of`] use the same
+    /// unit.
+    pub fn with_column_unit(mut self, unit: ColumnUnit) -> Self {
+        self.column_unit = unit;
+        self.column = self.column_at(self.offset);
+        self
+    }
+
+    pub const fn column_unit(&self) -> ColumnUnit {
+        self.column_unit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::XorShift;
+
+    const TEXT: &str = "a\u{e9}\u{1f600}b\n\u{1f600}c";
+
+    #[test]
+    fn test_column_units() {
+        let columns = |unit| {
+            let mut cursor = Cursor::new(TEXT).with_column_unit(unit);
+            let mut columns = vec![cursor.column()];
+            while cursor.next().is_some() {
+                columns.push(cursor.column());
+            }
+            columns
+        };
+        assert_eq!(columns(ColumnUnit::Char), [0, 1, 2, 3, 4, 0, 1, 2]);
+        assert_eq!(columns(ColumnUnit::Byte), [0, 1, 3, 7, 8, 0, 4, 5]);
+        assert_eq!(columns(ColumnUnit::Utf16), [0, 1, 2, 4, 5, 0, 2, 3]);
+
+        let mut cursor = Cursor::new(TEXT).with_column_unit(ColumnUnit::Utf16);
+        assert_eq!(cursor.seek_to_line_col(0, 4), Ok(()));
+        assert_eq!(cursor.peek_char(), Some('b'));
+        assert_eq!(cursor.offset_of(0, 3), Some(3));
+        assert_eq!(cursor.position_of(TEXT.len()), (1, 3));
+        let cursor = cursor.with_column_unit(ColumnUnit::Char);
+        assert_eq!(cursor.column(), 3);
+    }
+
+    #[test]
+    fn test_columns_agree_after_any_move() {
+        let mut rng = XorShift(11);
+        for unit in [ColumnUnit::Byte, ColumnUnit::Utf16] {
+            for _ in 0..100 {
+                let text = rng.string(&['a', '\u{e9}', '\u{1f600}', '\n'], 0, 20);
+                let mut cursor = Cursor::new(&text).with_column_unit(unit);
+                for _ in 0..10 {
+                    let steps = rng.next() as usize % 6;
+                    match rng.next() % 3 {
+                        0 => drop(cursor.advance_by(steps)),
+                        1 => drop(cursor.retreat_by(steps)),
+                        _ => drop(cursor.prev()),
+                    }
+                    let line_start = cursor.consumed().rfind('\n').map_or(0, |i| i + 1);
+                    let line = &cursor.consumed()[line_start..];
+                    assert_eq!(cursor.column(), unit.width(line));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,233 @@
+use std::ops::Range;
+
+use crate::Cursor;
+
+type Field<'a> = (Range<usize>, &'a str);
+
+/// Cuts `line`, which starts at byte offset `base`, into one field per
+/// width, each starting at the column where the previous one ended. The
+/// last field takes the rest of the line.
+fn split_columns<'a>(
+    line: &'a str,
+    base: usize,
+    widths: &[usize],
+    char_width: fn(char) -> usize,
+    trim: bool,
+) -> Vec<Field<'a>> {
+    let mut fields = Vec::with_capacity(widths.len());
+    let mut chars = line.char_indices().peekable();
+    let (mut column, mut boundary, mut start) = (0, 0, 0);
+    for (i, &width) in widths.iter().enumerate() {
+        let end = if i + 1 == widths.len() {
+            line.len()
+        } else {
+            boundary += width;
+            // A char belongs to the field its first cell falls in.
+            while let Some(&(_, c)) = chars.peek().filter(|_| column < boundary) {
+                column += char_width(c);
+                chars.next();
+            }
+            chars.peek().map_or(line.len(), |&(at, _)| at)
+        };
+        let mut field = start..end;
+        if trim {
+            let text = &line[field.clone()];
+            field.start += text.len() - text.trim_start().len();
+            field.end = field.start + text.trim().len();
+        }
+        fields.push((base + field.start..base + field.end, &line[field]));
+        start = end;
+    }
+    fields
+}
+
+fn chars_width(_: char) -> usize {
+    1
+}
+
+#[cfg(feature = "width")]
+fn cells_width(c: char) -> usize {
+    unicode_width::UnicodeWidthChar::width(c).unwrap_or(0)
+}
+
+/// Iterator over the remaining lines cut into fixed-width fields; see
+/// [`Cursor::columns`].
+pub struct Columns<'a, 'b, 'w> {
+    cursor: &'b mut Cursor<'a>,
+    widths: &'w [usize],
+    char_width: fn(char) -> usize,
+    trim: bool,
+}
+
+impl Columns<'_, '_, '_> {
+    /// Whether to trim whitespace around each field. Off by default.
+    pub fn trim(mut self, trim: bool) -> Self {
+        self.trim = trim;
+        self
+    }
+
+    /// Measures widths in terminal display cells, so that a wide CJK char
+    /// takes two columns, instead of in chars.
+    #[cfg(feature = "width")]
+    pub fn display_width(mut self, display: bool) -> Self {
+        self.char_width = if display { cells_width } else { chars_width };
+        self
+    }
+}
+
+impl<'a> Cursor<'a> {
+    /// Reads one line and cuts it into fields of the given widths, counted in
+    /// chars, with the last field taking the rest of the line. A short line
+    /// yields empty fields at its end.
+    pub fn next_columns(&mut self, widths: &[usize]) -> Option<Vec<Field<'a>>> {
+        self.columns(widths).next()
+    }
+
+    /// Applies [`Cursor::next_columns`] to every remaining line.
+    pub fn columns<'w>(&mut self, widths: &'w [usize]) -> Columns<'a, '_, 'w> {
+        Columns {
+            cursor: self,
+            widths,
+            char_width: chars_width,
+            trim: false,
+        }
+    }
+}
+
+impl<'a> Iterator for Columns<'a, '_, '_> {
+    type Item = Vec<Field<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (start, line) = self.cursor.next_line()?;
+        Some(split_columns(
+            line,
+            start,
+            self.widths,
+            self.char_width,
+            self.trim,
+        ))
+    }
+}
+
+/// The char ranges between the gutters of `lines`: the columns that are
+/// whitespace, or past the end, on every line that isn't blank.
+fn infer_fields<'a>(lines: impl Iterator<Item = &'a str>) -> Vec<Range<usize>> {
+    let mut blank: Vec<bool> = Vec::new();
+    for line in lines.filter(|line| !line.trim().is_empty()) {
+        for (i, c) in line.chars().enumerate() {
+            if i == blank.len() {
+                blank.push(true);
+            }
+            blank[i] &= c.is_whitespace();
+        }
+    }
+    let mut fields = Vec::new();
+    let mut start = None;
+    for (i, &gutter) in blank.iter().chain([&true]).enumerate() {
+        match (start, gutter) {
+            (None, false) => start = Some(i),
+            (Some(s), true) => {
+                fields.push(s..i);
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    fields
+}
+
+impl<'a> Cursor<'a> {
+    /// The remaining lines, split where the cursor's terminator says,
+    /// without moving the cursor.
+    fn sample_lines(&self) -> impl Iterator<Item = &'a str> {
+        let mut ahead = self.clone();
+        std::iter::from_fn(move || ahead.next_line().map(|(_, line)| line))
+    }
+
+    /// Infers the fields of space-aligned text from up to `max_lines` of the
+    /// remaining lines, as char ranges separated by columns that are blank on
+    /// every non-blank line. Columns past the end of a short line count as
+    /// blank. The cursor does not move.
+    pub fn detect_columns(&self, max_lines: usize) -> Vec<Range<usize>> {
+        infer_fields(self.sample_lines().take(max_lines))
+    }
+
+    /// Like [`Cursor::detect_columns`], but ignores the first line, for a
+    /// header aligned differently from the rows below it.
+    pub fn detect_columns_after_header(&self, max_lines: usize) -> Vec<Range<usize>> {
+        infer_fields(self.sample_lines().skip(1).take(max_lines))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LineTerminator;
+
+    const PS: &str = "  PID TTY      CMD\r\n  101 pts/0    bash -l\n    7 ?\n";
+
+    fn texts<'a>(fields: &[Field<'a>]) -> Vec<&'a str> {
+        fields.iter().map(|(_, text)| *text).collect()
+    }
+
+    #[test]
+    fn test_next_columns() {
+        let mut cursor = Cursor::new(PS);
+        let header = cursor.next_columns(&[5, 9, 0]).unwrap();
+        assert_eq!(texts(&header), ["  PID", " TTY     ", " CMD"]);
+        assert_eq!(header[1].0, 5..14);
+
+        let rows: Vec<_> = cursor.columns(&[5, 9, 0]).trim(true).collect();
+        assert_eq!(texts(&rows[0]), ["101", "pts/0", "bash -l"]);
+        assert_eq!(rows[0][2], (35..42, "bash -l"));
+        assert_eq!(texts(&rows[1]), ["7", "?", ""]);
+        assert_eq!(rows[1][2].0, 50..50);
+        assert_eq!(rows.len(), 2);
+    }
+
+    #[test]
+    fn test_widths_count_chars() {
+        let mut cursor = Cursor::new("héllo wörld\nab");
+        let fields = cursor.next_columns(&[3, 2, 1]).unwrap();
+        assert_eq!(fields, [(0..4, "hél"), (4..6, "lo"), (6..13, " wörld")]);
+        let short = cursor.next_columns(&[3, 2, 1]).unwrap();
+        assert_eq!(short, [(14..16, "ab"), (16..16, ""), (16..16, "")]);
+    }
+
+    #[test]
+    fn test_detect_columns() {
+        let text = "  PID TTY      CMD\n  101 pts/0    bash\n\n    7 ?        zsh -l\n";
+        let cursor = Cursor::new(text);
+        assert_eq!(cursor.detect_columns(10), [2..5, 6..11, 15..21]);
+        assert_eq!(cursor.detect_columns(2), [2..5, 6..11, 15..19]);
+        assert_eq!(cursor.peek(), Some((0, ' ')));
+        assert!(Cursor::new("").detect_columns(5).is_empty());
+
+        let cr = text.replace('\n', "\r");
+        let cursor = Cursor::new(&cr).with_line_terminator(LineTerminator::Ascii);
+        assert_eq!(cursor.detect_columns(10), [2..5, 6..11, 15..21]);
+        let ls = text.replace('\n', "\u{2028}");
+        let cursor = Cursor::new(&ls).with_line_terminator(LineTerminator::Unicode);
+        assert_eq!(
+            cursor.detect_columns_after_header(10),
+            [2..5, 6..11, 15..21]
+        );
+    }
+
+    #[test]
+    fn test_detect_columns_after_header() {
+        let cursor = Cursor::new("NAME SIZE\nalpha  1024\nbeta     12\n");
+        let merged = cursor.detect_columns(10);
+        assert_eq!((merged.len(), merged[0].clone()), (1, 0..11));
+        assert_eq!(cursor.detect_columns_after_header(10), [0..5, 7..11]);
+    }
+
+    #[cfg(feature = "width")]
+    #[test]
+    fn test_display_width() {
+        let mut cursor = Cursor::new("名前 age\n");
+        let mut columns = cursor.columns(&[5, 0]).display_width(true);
+        let fields = columns.next().unwrap();
+        assert_eq!(texts(&fields), ["名前 ", "age"]);
+    }
+}
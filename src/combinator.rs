@@ -0,0 +1,641 @@
+use crate::{Cursor, ParseError, Span};
+
+/// Classifies an error as recoverable ("this alternative does not apply",
+/// so backtrack and try another) or fatal ("this alternative applies but is
+/// malformed", so stop and report it).
+///
+/// Errors that carry no classification, such as `&str` or `String`, are
+/// always recoverable. [`ParseError`]s become fatal through [`Cursor::cut`].
+pub trait Recoverable {
+    fn is_recoverable(&self) -> bool;
+}
+
+/// One branch of [`Cursor::one_of`].
+pub type Alternative<'a, 'f, T, E> = &'f mut dyn FnMut(&mut Cursor<'a>) -> Result<T, E>;
+
+impl Recoverable for () {
+    fn is_recoverable(&self) -> bool {
+        true
+    }
+}
+
+impl Recoverable for &str {
+    fn is_recoverable(&self) -> bool {
+        true
+    }
+}
+
+impl Recoverable for String {
+    fn is_recoverable(&self) -> bool {
+        true
+    }
+}
+
+/// Whether a separated list may, or must, end with a separator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Trailing {
+    Forbidden,
+    Allowed,
+    Required,
+}
+
+/// Options for [`Cursor::parse_separated`]. By default whitespace around
+/// separators is skipped and at least one item is required.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Separated {
+    trailing: Trailing,
+    skip_whitespace: bool,
+    allow_empty: bool,
+}
+
+impl Separated {
+    pub const fn new(trailing: Trailing) -> Self {
+        Self {
+            trailing,
+            skip_whitespace: true,
+            allow_empty: false,
+        }
+    }
+
+    pub const fn skip_whitespace(mut self, skip: bool) -> Self {
+        self.skip_whitespace = skip;
+        self
+    }
+
+    pub const fn allow_empty(mut self, allow: bool) -> Self {
+        self.allow_empty = allow;
+        self
+    }
+}
+
+impl From<Trailing> for Separated {
+    fn from(trailing: Trailing) -> Self {
+        Self::new(trailing)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SeparatedError<E> {
+    /// The item parser failed where an item was required.
+    Item(E),
+    /// [`Trailing::Required`] was set but the list ended without a separator
+    /// at `offset`.
+    MissingTrailing { offset: usize },
+    /// A separator and item together consumed nothing at `offset`, as with
+    /// an empty separator, so the list would never end.
+    NoProgress { offset: usize },
+}
+
+impl<E: Recoverable> Recoverable for SeparatedError<E> {
+    fn is_recoverable(&self) -> bool {
+        match self {
+            Self::Item(err) => err.is_recoverable(),
+            Self::MissingTrailing { .. } => true,
+            Self::NoProgress { .. } => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BetweenError<E> {
+    /// The opening delimiter was not found at `offset`; nothing was consumed.
+    MissingOpen { offset: usize },
+    /// The inner parser failed; the cursor is left where it stopped.
+    Inner(E),
+    /// The closing delimiter was missing. `span` runs from the opener to
+    /// where the closer was expected.
+    MissingClose { span: Span },
+}
+
+impl<E: Recoverable> Recoverable for BetweenError<E> {
+    fn is_recoverable(&self) -> bool {
+        match self {
+            Self::Inner(err) => err.is_recoverable(),
+            Self::MissingOpen { .. } | Self::MissingClose { .. } => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ManyError<E> {
+    /// Only `found` items parsed before `error`, fewer than the `min` required.
+    TooFew { min: usize, found: usize, error: E },
+    /// The parser succeeded at `offset` without consuming anything, so
+    /// repeating it would never terminate.
+    NoProgress { offset: usize },
+    /// The parser failed with a fatal error; the cursor is left where it
+    /// stopped.
+    Fatal(E),
+}
+
+impl<E> Recoverable for ManyError<E> {
+    fn is_recoverable(&self) -> bool {
+        matches!(self, Self::TooFew { .. })
+    }
+}
+
+impl<'a> Cursor<'a> {
+    /// Runs `f`, restoring the cursor to where it started if `f` fails with a
+    /// recoverable error. A fatal error leaves the cursor where `f` stopped.
+    pub fn attempt<T, E: Recoverable>(
+        &mut self,
+        f: impl FnOnce(&mut Cursor<'a>) -> Result<T, E>,
+    ) -> Result<T, E> {
+        let start = self.checkpoint();
+        let result = f(self);
+        if matches!(&result, Err(err) if err.is_recoverable()) {
+            self.restore(start);
+        }
+        result
+    }
+
+    /// Runs `f`, restoring the cursor to where it started if `f` returns any
+    /// error. Unlike [`Cursor::attempt`], this ignores [`Recoverable`].
+    pub fn try_parse<F, T, E>(&mut self, f: F) -> Result<T, E>
+    where
+        F: FnOnce(&mut Cursor<'a>) -> Result<T, E>,
+    {
+        let start = self.checkpoint();
+        let result = f(self);
+        if result.is_err() {
+            self.restore(start);
+        }
+        result
+    }
+
+    /// Runs `f`, making any error it returns fatal so that enclosing
+    /// [`Cursor::attempt`]s and [`Cursor::one_of`]s stop backtracking.
+    pub fn cut<T>(
+        &mut self,
+        f: impl FnOnce(&mut Cursor<'a>) -> Result<T, ParseError>,
+    ) -> Result<T, ParseError> {
+        f(self).map_err(ParseError::into_fatal)
+    }
+
+    /// Tries each alternative in turn, backtracking over recoverable errors.
+    ///
+    /// Returns the first success or the first fatal error, or the last
+    /// recoverable error if every alternative fails.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `alternatives` is empty.
+    pub fn one_of<T, E: Recoverable>(
+        &mut self,
+        alternatives: &mut [Alternative<'a, '_, T, E>],
+    ) -> Result<T, E> {
+        let mut last = None;
+        for alternative in alternatives {
+            match self.attempt(|c| alternative(c)) {
+                Err(err) if err.is_recoverable() => last = Some(err),
+                result => return result,
+            }
+        }
+        Err(last.expect("one_of: no alternatives"))
+    }
+
+    /// Parses `open`, then `f`, then `close`.
+    ///
+    /// Only a missing opener leaves the cursor untouched; wrap the call in
+    /// [`Cursor::attempt`] to backtrack over the other failures too.
+    pub fn between<T, E>(
+        &mut self,
+        open: &str,
+        close: &str,
+        f: impl FnOnce(&mut Cursor<'a>) -> Result<T, E>,
+    ) -> Result<T, BetweenError<E>> {
+        self.between_impl(open, close, false, f)
+    }
+
+    /// Like [`Cursor::between`], but skips whitespace after `open` and before
+    /// `close`.
+    pub fn between_trimmed<T, E>(
+        &mut self,
+        open: &str,
+        close: &str,
+        f: impl FnOnce(&mut Cursor<'a>) -> Result<T, E>,
+    ) -> Result<T, BetweenError<E>> {
+        self.between_impl(open, close, true, f)
+    }
+
+    fn between_impl<T, E>(
+        &mut self,
+        open: &str,
+        close: &str,
+        trim: bool,
+        f: impl FnOnce(&mut Cursor<'a>) -> Result<T, E>,
+    ) -> Result<T, BetweenError<E>> {
+        let start = self.offset;
+        if !self.eat_str(open) {
+            return Err(BetweenError::MissingOpen { offset: start });
+        }
+        if trim {
+            self.skip_whitespace();
+        }
+        let value = f(self).map_err(BetweenError::Inner)?;
+        if trim {
+            self.skip_whitespace();
+        }
+        if !self.eat_str(close) {
+            let span = Span::new(start, self.offset);
+            return Err(BetweenError::MissingClose { span });
+        }
+        Ok(value)
+    }
+
+    /// Applies `f` repeatedly, at least `min` and at most `max` times.
+    ///
+    /// The final failed attempt is rolled back, so on success the cursor sits
+    /// right after the last item. A fatal error from `f` is returned as
+    /// [`ManyError::Fatal`] without rewinding; on any other error the cursor
+    /// is restored to where it started.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max` is less than `min`.
+    pub fn many<T, E: Recoverable>(
+        &mut self,
+        min: usize,
+        max: Option<usize>,
+        mut f: impl FnMut(&mut Cursor<'a>) -> Result<T, E>,
+    ) -> Result<Vec<T>, ManyError<E>> {
+        assert!(max.is_none_or(|max| max >= min), "many: max < min");
+        let start = self.checkpoint();
+        let mut items = Vec::new();
+
+        while max.is_none_or(|max| items.len() < max) {
+            let before = self.offset;
+            match self.attempt(&mut f) {
+                Ok(_) if self.offset == before => {
+                    self.restore(start);
+                    return Err(ManyError::NoProgress { offset: before });
+                }
+                Ok(value) => items.push(value),
+                Err(error) if !error.is_recoverable() => return Err(ManyError::Fatal(error)),
+                Err(error) if items.len() < min => {
+                    self.restore(start);
+                    let found = items.len();
+                    return Err(ManyError::TooFew { min, found, error });
+                }
+                Err(_) => break,
+            }
+        }
+        Ok(items)
+    }
+
+    /// Applies `f` zero or more times.
+    pub fn many0<T, E: Recoverable>(
+        &mut self,
+        f: impl FnMut(&mut Cursor<'a>) -> Result<T, E>,
+    ) -> Result<Vec<T>, ManyError<E>> {
+        self.many(0, None, f)
+    }
+
+    /// Applies `f` one or more times.
+    pub fn many1<T, E: Recoverable>(
+        &mut self,
+        f: impl FnMut(&mut Cursor<'a>) -> Result<T, E>,
+    ) -> Result<Vec<T>, ManyError<E>> {
+        self.many(1, None, f)
+    }
+
+    /// Parses `item (sep item)*`, optionally followed by a trailing `sep`.
+    ///
+    /// On a recoverable error the cursor is restored to where the list
+    /// started; a fatal item error leaves it where the item stopped. On success
+    /// it is left after the last item, or after the trailing separator if
+    /// there was one. With [`Trailing::Forbidden`] an item that fails after a
+    /// separator is an error; otherwise it ends the list at that separator.
+    /// A separator and item that consume nothing between them fail with
+    /// [`SeparatedError::NoProgress`] rather than repeat forever.
+    pub fn parse_separated<T, E: Recoverable>(
+        &mut self,
+        mut item: impl FnMut(&mut Cursor<'a>) -> Result<T, E>,
+        sep: &str,
+        options: impl Into<Separated>,
+    ) -> Result<Vec<T>, SeparatedError<E>> {
+        let options = options.into();
+        let start = self.checkpoint();
+        let mut items = Vec::new();
+
+        match item(self) {
+            Ok(value) => items.push(value),
+            Err(err) if !err.is_recoverable() => return Err(SeparatedError::Item(err)),
+            Err(_) if options.allow_empty => {
+                self.restore(start);
+                return Ok(items);
+            }
+            Err(err) => {
+                self.restore(start);
+                return Err(SeparatedError::Item(err));
+            }
+        }
+
+        let mut trailing = false;
+        loop {
+            let round = self.offset;
+            let before_sep = self.checkpoint();
+            if options.skip_whitespace {
+                self.skip_whitespace();
+            }
+            if !self.eat_str(sep) {
+                self.restore(before_sep);
+                break;
+            }
+
+            let after_sep = self.checkpoint();
+            if options.skip_whitespace {
+                self.skip_whitespace();
+            }
+            match item(self) {
+                Ok(_) if self.offset == round => {
+                    self.restore(start);
+                    return Err(SeparatedError::NoProgress { offset: round });
+                }
+                Ok(value) => items.push(value),
+                Err(err) if !err.is_recoverable() => return Err(SeparatedError::Item(err)),
+                Err(err) if options.trailing == Trailing::Forbidden => {
+                    self.restore(start);
+                    return Err(SeparatedError::Item(err));
+                }
+                Err(_) => {
+                    self.restore(after_sep);
+                    trailing = true;
+                    break;
+                }
+            }
+        }
+
+        if options.trailing == Trailing::Required && !trailing {
+            let offset = self.offset;
+            self.restore(start);
+            return Err(SeparatedError::MissingTrailing { offset });
+        }
+        Ok(items)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digit(cursor: &mut Cursor) -> Result<u32, &'static str> {
+        match cursor.peek_char().and_then(|c| c.to_digit(10)) {
+            Some(d) => {
+                cursor.next();
+                Ok(d)
+            }
+            None => Err("expected digit"),
+        }
+    }
+
+    #[test]
+    fn test_try_parse_restores_on_any_error() {
+        let mut cursor = Cursor::new("let x");
+        let result = cursor.try_parse(let_binding);
+        assert!(result.unwrap_err().is_fatal());
+        assert_eq!(cursor.offset(), 0);
+
+        assert_eq!(
+            cursor.try_parse(|c| c.eat_str("let").then_some(()).ok_or(())),
+            Ok(())
+        );
+        assert_eq!(cursor.offset(), 3);
+    }
+
+    #[test]
+    fn test_attempt_rolls_back() {
+        let mut cursor = Cursor::new("12");
+        let result = cursor.attempt(|c| {
+            digit(c)?;
+            c.eat_str("x").then_some(()).ok_or("expected x")
+        });
+        assert_eq!(result, Err("expected x"));
+        assert_eq!(cursor.peek(), Some((0, '1')));
+    }
+
+    /// `let <digit>`: once `let` is seen, a missing digit is a syntax error.
+    fn let_binding(cursor: &mut Cursor) -> Result<u32, ParseError> {
+        if !cursor.eat_str("let ") {
+            return Err(cursor.error("expected `let`"));
+        }
+        cursor.cut(|c| digit(c).map_err(|msg| c.error(msg)))
+    }
+
+    fn expression(cursor: &mut Cursor) -> Result<u32, ParseError> {
+        digit(cursor).map_err(|msg| cursor.error(msg))
+    }
+
+    #[test]
+    fn test_attempt_over_cut_keeps_failure_position() {
+        let mut cursor = Cursor::new("let x");
+        let err = cursor.attempt(let_binding).unwrap_err();
+        assert!(err.is_fatal());
+        assert_eq!(err.offset(), 4);
+        assert_eq!(cursor.peek(), Some((4, 'x')));
+    }
+
+    #[test]
+    fn test_cut_over_attempt_rewinds_then_turns_fatal() {
+        let mut cursor = Cursor::new("let x");
+        cursor.eat_str("let");
+        let err = cursor
+            .cut(|c| {
+                c.attempt(|c| {
+                    c.eat_str(" ");
+                    expression(c)
+                })
+            })
+            .unwrap_err();
+        assert!(err.is_fatal());
+        assert_eq!(cursor.peek(), Some((3, ' ')));
+    }
+
+    #[test]
+    fn test_one_of_respects_fatal_errors() {
+        let mut cursor = Cursor::new("7");
+        let value = cursor.one_of(&mut [&mut let_binding, &mut expression]);
+        assert_eq!(value, Ok(7));
+
+        let mut cursor = Cursor::new("let ?");
+        let mut reached_second = false;
+        let mut second = |c: &mut Cursor| {
+            reached_second = true;
+            expression(c)
+        };
+        let err = cursor
+            .one_of(&mut [&mut let_binding, &mut second])
+            .unwrap_err();
+        assert!(err.is_fatal());
+        assert_eq!(err.offset(), 4);
+        assert!(!reached_second);
+
+        let mut cursor = Cursor::new("?");
+        let err = cursor.one_of(&mut [&mut let_binding, &mut expression]);
+        assert_eq!(err.unwrap_err().message(), "expected digit");
+    }
+
+    #[test]
+    fn test_many_stops_at_fatal_error() {
+        let mut cursor = Cursor::new("let 1let 2let x");
+        let result = cursor.many0(let_binding);
+        assert!(matches!(result, Err(ManyError::Fatal(ref err)) if err.offset() == 14));
+        assert_eq!(cursor.peek(), Some((14, 'x')));
+    }
+
+    #[test]
+    fn test_between() {
+        let mut cursor = Cursor::new("(7)");
+        assert_eq!(cursor.between("(", ")", digit), Ok(7));
+        assert_eq!(cursor.next(), None);
+
+        let mut cursor = Cursor::new("[ 7 ]");
+        assert_eq!(cursor.between_trimmed("[", "]", digit), Ok(7));
+        let mut cursor = Cursor::new("[ 7 ]");
+        assert!(cursor.between("[", "]", digit).is_err());
+    }
+
+    #[test]
+    fn test_between_errors() {
+        let mut cursor = Cursor::new("x7)");
+        let result = cursor.between("(", ")", digit);
+        assert_eq!(result, Err(BetweenError::MissingOpen { offset: 0 }));
+        assert_eq!(cursor.peek(), Some((0, 'x')));
+
+        let mut cursor = Cursor::new("(x)");
+        let result = cursor.between("(", ")", digit);
+        assert_eq!(result, Err(BetweenError::Inner("expected digit")));
+        assert_eq!(cursor.peek(), Some((1, 'x')));
+
+        let mut cursor = Cursor::new("(78");
+        let result = cursor.between("(", ")", digit);
+        let span = Span::new(0, 2);
+        assert_eq!(result, Err(BetweenError::MissingClose { span }));
+    }
+
+    #[test]
+    fn test_between_backtracks_with_attempt() {
+        let mut cursor = Cursor::new("[7]");
+        let value = cursor
+            .attempt(|c| c.between("(", ")", digit))
+            .or_else(|_| cursor.attempt(|c| c.between("[", "]", digit)));
+        assert_eq!(value, Ok(7));
+
+        let mut cursor = Cursor::new("(7]");
+        let value = cursor.attempt(|c| c.between("(", ")", digit));
+        assert!(matches!(value, Err(BetweenError::MissingClose { .. })));
+        assert_eq!(cursor.peek(), Some((0, '(')));
+    }
+
+    fn digit_pair(cursor: &mut Cursor) -> Result<u32, &'static str> {
+        Ok(digit(cursor)? * 10 + digit(cursor)?)
+    }
+
+    #[test]
+    fn test_many_rolls_back_final_attempt() {
+        let mut cursor = Cursor::new("12345");
+        assert_eq!(cursor.many0(digit_pair), Ok(vec![12, 34]));
+        assert_eq!(cursor.peek(), Some((4, '5')));
+    }
+
+    #[test]
+    fn test_many_bounds() {
+        let mut cursor = Cursor::new("12345");
+        assert_eq!(cursor.many(1, Some(3), digit), Ok(vec![1, 2, 3]));
+        assert_eq!(cursor.peek_char(), Some('4'));
+
+        let mut cursor = Cursor::new("12x");
+        let result = cursor.many(3, None, digit);
+        let error = "expected digit";
+        assert_eq!(
+            result,
+            Err(ManyError::TooFew {
+                min: 3,
+                found: 2,
+                error
+            })
+        );
+        assert_eq!(cursor.peek(), Some((0, '1')));
+
+        let mut cursor = Cursor::new("x");
+        assert!(cursor.many1(digit).is_err());
+        assert_eq!(cursor.many0(digit), Ok(vec![]));
+        assert_eq!(cursor.many(0, Some(0), digit), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_many_detects_no_progress() {
+        let mut cursor = Cursor::new("1x");
+        let result = cursor.many0(|c| {
+            c.eat_str("1");
+            Ok::<_, ()>(())
+        });
+        assert_eq!(result, Err(ManyError::NoProgress { offset: 1 }));
+        assert_eq!(cursor.peek(), Some((0, '1')));
+    }
+
+    #[test]
+    fn test_separated_with_whitespace() {
+        let mut cursor = Cursor::new("1 , 2,3 ]");
+        let items = cursor.parse_separated(digit, ",", Trailing::Forbidden);
+        assert_eq!(items, Ok(vec![1, 2, 3]));
+        assert_eq!(cursor.peek(), Some((7, ' ')));
+    }
+
+    #[test]
+    fn test_separated_trailing_policies() {
+        let mut cursor = Cursor::new("1, 2, ]");
+        let items = cursor.parse_separated(digit, ",", Trailing::Allowed);
+        assert_eq!(items, Ok(vec![1, 2]));
+        assert_eq!(cursor.peek(), Some((5, ' ')));
+
+        let mut cursor = Cursor::new("1, 2, ]");
+        let items = cursor.parse_separated(digit, ",", Trailing::Forbidden);
+        assert_eq!(items, Err(SeparatedError::Item("expected digit")));
+        assert_eq!(cursor.peek(), Some((0, '1')));
+
+        let mut cursor = Cursor::new("1, 2 ]");
+        let items = cursor.parse_separated(digit, ",", Trailing::Required);
+        assert_eq!(items, Err(SeparatedError::MissingTrailing { offset: 4 }));
+        assert_eq!(cursor.peek(), Some((0, '1')));
+
+        let mut cursor = Cursor::new("1,2,]");
+        let items = cursor.parse_separated(digit, ",", Trailing::Required);
+        assert_eq!(items, Ok(vec![1, 2]));
+        assert_eq!(cursor.peek_char(), Some(']'));
+    }
+
+    #[test]
+    fn test_separated_without_progress() {
+        let mut cursor = Cursor::new("1,2");
+        let nothing = |_: &mut Cursor| Ok::<_, &str>(());
+        let items = cursor.parse_separated(nothing, "", Trailing::Allowed);
+        assert_eq!(items, Err(SeparatedError::NoProgress { offset: 0 }));
+        assert!(cursor.is_at_start());
+
+        let items = cursor.parse_separated(digit, "", Trailing::Allowed);
+        assert_eq!(items, Ok(vec![1]));
+        assert_eq!(cursor.offset(), 1);
+    }
+
+    #[test]
+    fn test_separated_empty_list() {
+        let mut cursor = Cursor::new("]");
+        let items = cursor.parse_separated(digit, ",", Trailing::Allowed);
+        assert_eq!(items, Err(SeparatedError::Item("expected digit")));
+
+        let options = Separated::new(Trailing::Required).allow_empty(true);
+        let items = cursor.parse_separated(digit, ",", options);
+        assert_eq!(items, Ok(vec![]));
+        assert_eq!(cursor.peek_char(), Some(']'));
+    }
+
+    #[test]
+    fn test_separated_without_whitespace_skipping() {
+        let mut cursor = Cursor::new("1,2 ,3");
+        let options = Separated::new(Trailing::Forbidden).skip_whitespace(false);
+        let items = cursor.parse_separated(digit, ",", options);
+        assert_eq!(items, Ok(vec![1, 2]));
+        assert_eq!(cursor.peek_char(), Some(' '));
+    }
+}
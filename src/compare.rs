@@ -0,0 +1,68 @@
+use crate::Cursor;
+
+/// The whitespace-separated runs of `s` with their byte offsets.
+fn runs(s: &str) -> impl Iterator<Item = (usize, &str)> {
+    let base = s.as_ptr() as usize;
+    s.split_whitespace()
+        .map(move |run| (run.as_ptr() as usize - base, run))
+}
+
+impl Cursor<'_> {
+    /// Whether the remaining input equals `expected` once every whitespace
+    /// run on either side is treated as one separator and leading and
+    /// trailing whitespace is ignored.
+    pub fn matches_ignoring_whitespace(&self, expected: &str) -> bool {
+        self.diff_ignoring_whitespace(expected).is_none()
+    }
+
+    /// Finds the first difference that [`Cursor::matches_ignoring_whitespace`]
+    /// would not ignore, as the offset of the differing run in the input
+    /// and in `expected`. A side that ran out of runs reports its length.
+    pub fn diff_ignoring_whitespace(&self, expected: &str) -> Option<(usize, usize)> {
+        let mut actual_runs = runs(self.forward());
+        let mut expected_runs = runs(expected);
+        loop {
+            match (actual_runs.next(), expected_runs.next()) {
+                (None, None) => return None,
+                (Some((a, x)), Some((b, y))) if x != y => return Some((self.offset + a, b)),
+                (Some(_), Some(_)) => {}
+                (Some((a, _)), None) => return Some((self.offset + a, expected.len())),
+                (None, Some((b, _))) => return Some((self.data.len(), b)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_whitespace_runs_collapse() {
+        let cursor = Cursor::new("  fn main() {\n\t  x\u{a0}+ y }\n");
+        assert!(cursor.matches_ignoring_whitespace("fn main() { x + y }"));
+        assert!(cursor.matches_ignoring_whitespace("fn\u{3000}main()\n{ x + y }"));
+        assert!(!cursor.matches_ignoring_whitespace("fn main() {x+y}"));
+        assert!(Cursor::new(" \n").matches_ignoring_whitespace(""));
+    }
+
+    #[test]
+    fn test_first_difference() {
+        let mut cursor = Cursor::new("let a = b;\nlet c = d;");
+        assert_eq!(
+            cursor.diff_ignoring_whitespace("let a = b;  let c = e;"),
+            Some((19, 20))
+        );
+        assert_eq!(
+            cursor.diff_ignoring_whitespace("let a = b;"),
+            Some((11, 10))
+        );
+        assert_eq!(
+            cursor.diff_ignoring_whitespace("let a = b; let c = d; x"),
+            Some((21, 22))
+        );
+
+        cursor.take_until("\n");
+        assert_eq!(cursor.diff_ignoring_whitespace("let cc"), Some((15, 4)));
+    }
+}
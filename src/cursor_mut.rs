@@ -0,0 +1,543 @@
+use std::borrow::Cow;
+use std::ops::Range;
+
+use crate::{Checkpoint, ColumnUnit, Cursor, Edit, LineTerminator, Position, SeekError, Span};
+
+/// Storage for the text of a [`CursorMut`]: a `String`, a `&mut String`, a
+/// [`GapBuffer`](crate::GapBuffer) or a [`PieceTable`](crate::PieceTable).
+pub trait TextBuffer {
+    /// Byte length of the text.
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn is_char_boundary(&self, offset: usize) -> bool;
+
+    /// The text in `range`, which must lie on char boundaries. Borrowed when
+    /// the storage holds it in one piece.
+    fn slice(&self, range: Range<usize>) -> Cow<'_, str>;
+
+    /// Replaces the text in `range` with `s`. Panics like
+    /// [`String::replace_range`] if `range` is out of bounds or splits a
+    /// char.
+    fn replace_range(&mut self, range: Range<usize>, s: &str);
+
+    /// The whole text as one slice, rearranging the storage if it has to.
+    fn make_contiguous(&mut self) -> &str;
+}
+
+impl TextBuffer for String {
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    fn is_char_boundary(&self, offset: usize) -> bool {
+        str::is_char_boundary(self, offset)
+    }
+
+    fn slice(&self, range: Range<usize>) -> Cow<'_, str> {
+        Cow::Borrowed(&self[range])
+    }
+
+    fn replace_range(&mut self, range: Range<usize>, s: &str) {
+        String::replace_range(self, range, s);
+    }
+
+    fn make_contiguous(&mut self) -> &str {
+        self
+    }
+}
+
+impl TextBuffer for &mut String {
+    fn len(&self) -> usize {
+        String::len(self)
+    }
+
+    fn is_char_boundary(&self, offset: usize) -> bool {
+        str::is_char_boundary(self, offset)
+    }
+
+    fn slice(&self, range: Range<usize>) -> Cow<'_, str> {
+        Cow::Borrowed(&self[range])
+    }
+
+    fn replace_range(&mut self, range: Range<usize>, s: &str) {
+        String::replace_range(self, range, s);
+    }
+
+    fn make_contiguous(&mut self) -> &str {
+        self
+    }
+}
+
+/// The first char boundary at or after `offset`.
+fn ceil_boundary(text: &impl TextBuffer, mut offset: usize) -> usize {
+    while !text.is_char_boundary(offset) {
+        offset += 1;
+    }
+    offset
+}
+
+/// The offset past the char at `offset`, or the end.
+fn next_boundary(text: &impl TextBuffer, offset: usize) -> usize {
+    if offset >= text.len() {
+        return text.len();
+    }
+    ceil_boundary(text, offset + 1)
+}
+
+/// Where the line containing `offset` starts, reading ever larger windows
+/// back from `offset`.
+fn line_start(text: &impl TextBuffer, offset: usize, terminator: LineTerminator) -> usize {
+    let end = next_boundary(text, offset);
+    let mut size = 256;
+    loop {
+        let start = ceil_boundary(text, offset.saturating_sub(size));
+        let window = text.slice(start..end);
+        if let Some(at) = terminator.nth_before(&window, offset - start, 0) {
+            return start + at;
+        }
+        if start == 0 {
+            return 0;
+        }
+        size *= 2;
+    }
+}
+
+/// A cursor over a [`TextBuffer`] it owns or borrows mutably, which can
+/// edit the text at its position while keeping its offset, line and column
+/// right. Moving and editing only read the text between the cursor and
+/// where it goes, plus the start of the line, so they stay cheap however
+/// long the text is.
+///
+/// For reading, [`CursorMut::cursor`] gives a [`Cursor`] at the same
+/// position.
+#[derive(Debug, Clone)]
+pub struct CursorMut<S = String> {
+    text: S,
+    position: Position,
+    terminator: LineTerminator,
+    column_unit: ColumnUnit,
+    history: Option<History>,
+}
+
+/// One edit, as recorded for [`CursorMut::undo`].
+#[derive(Debug, Clone)]
+struct Change {
+    start: usize,
+    removed: String,
+    inserted: String,
+    /// Where the cursor was before the edit and after it.
+    before: Position,
+    after: Position,
+}
+
+#[derive(Debug, Clone, Default)]
+struct History {
+    undo: Vec<Change>,
+    redo: Vec<Change>,
+}
+
+impl<S: TextBuffer> CursorMut<S> {
+    /// Creates a cursor at the start of `text`.
+    pub fn new(text: S) -> Self {
+        Self {
+            text,
+            position: Position::default(),
+            terminator: LineTerminator::Lf,
+            column_unit: ColumnUnit::Char,
+            history: None,
+        }
+    }
+
+    /// Records every edit from now on, so that [`CursorMut::undo`] and
+    /// [`CursorMut::redo`] can step through them.
+    pub fn with_undo(mut self) -> Self {
+        self.history.get_or_insert_with(History::default);
+        self
+    }
+
+    /// Uses `terminator` to decide where lines end, like
+    /// [`Cursor::with_line_terminator`].
+    pub fn with_line_terminator(mut self, terminator: LineTerminator) -> Self {
+        self.terminator = terminator;
+        let cursor = self.cursor().with_line_terminator(terminator);
+        self.position = Cursor::position(&cursor);
+        self
+    }
+
+    /// Counts columns in `unit`, like [`Cursor::with_column_unit`].
+    pub fn with_column_unit(mut self, unit: ColumnUnit) -> Self {
+        self.column_unit = unit;
+        let cursor = self.cursor().with_column_unit(unit);
+        self.position = Cursor::position(&cursor);
+        self
+    }
+
+    /// The whole text, which may mean rearranging the storage.
+    pub fn text(&mut self) -> &str {
+        self.text.make_contiguous()
+    }
+
+    pub fn into_inner(self) -> S {
+        self.text
+    }
+
+    /// A read-only cursor over the text at this cursor's position.
+    pub fn cursor(&mut self) -> Cursor<'_> {
+        let at = Checkpoint::at(self.position);
+        let (terminator, column_unit) = (self.terminator, self.column_unit);
+        let mut cursor = Cursor::new(self.text.make_contiguous());
+        cursor.terminator = terminator;
+        cursor.column_unit = column_unit;
+        cursor.restore(at);
+        cursor
+    }
+
+    pub fn offset(&self) -> usize {
+        self.position.byte
+    }
+
+    pub fn line(&self) -> usize {
+        self.position.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.position.column
+    }
+
+    pub fn position(&self) -> Position {
+        self.position
+    }
+
+    /// Moves to the byte offset `offset`, like [`Cursor::seek_to_offset`].
+    pub fn seek_to_offset(&mut self, offset: usize) -> Result<(), SeekError> {
+        let len = self.text.len();
+        if offset > len {
+            return Err(SeekError::OutOfRange { offset, len });
+        }
+        if !self.text.is_char_boundary(offset) {
+            return Err(SeekError::NotABoundary { offset });
+        }
+        self.position = self.walk_to(offset);
+        Ok(())
+    }
+
+    /// Moves forward `n` chars, or to the end, returning how many it moved.
+    pub fn advance_by(&mut self, n: usize) -> usize {
+        let (end, moved) = self.offset_after(n);
+        self.position = self.walk_to(end);
+        moved
+    }
+
+    /// Moves back `n` chars, or to the start, returning how many it moved.
+    pub fn retreat_by(&mut self, n: usize) -> usize {
+        let (start, moved) = self.offset_before(n);
+        self.position = self.walk_to(start);
+        moved
+    }
+
+    /// Inserts `s` at the cursor, leaving the cursor after it.
+    pub fn insert_str(&mut self, s: &str) {
+        let at = self.offset();
+        self.replace_range(at..at, s);
+    }
+
+    /// Deletes up to `n` chars after the cursor, returning them.
+    pub fn delete_forward(&mut self, n: usize) -> String {
+        let (end, _) = self.offset_after(n);
+        self.replace_range(self.offset()..end, "")
+    }
+
+    /// Deletes up to `n` chars before the cursor, returning them.
+    pub fn delete_backward(&mut self, n: usize) -> String {
+        let (start, _) = self.offset_before(n);
+        self.replace_range(start..self.offset(), "")
+    }
+
+    /// Replaces the text in `span` with `s`, returning the text it
+    /// replaced. The cursor moves as [`Edit::map_offset`] says: it keeps its
+    /// place in the text around the edit, and moves to the start of the
+    /// edit if it was inside.
+    ///
+    /// # Panics
+    ///
+    /// Panics like [`String::replace_range`] if `span` is out of range or
+    /// splits a char.
+    pub fn replace_range(&mut self, span: impl Into<Span>, s: &str) -> String {
+        let span = span.into();
+        let before = self.position;
+        let removed = self.splice(span, s);
+        if let Some(history) = &mut self.history {
+            history.redo.clear();
+            history.undo.push(Change {
+                start: span.start,
+                removed: removed.clone(),
+                inserted: s.to_owned(),
+                before,
+                after: self.position,
+            });
+        }
+        removed
+    }
+
+    /// Reverts the last edit not yet undone, putting the cursor back where
+    /// it was before the edit. Returns whether there was one; without
+    /// [`CursorMut::with_undo`] there never is.
+    pub fn undo(&mut self) -> bool {
+        let Some(change) = self.history.as_mut().and_then(|h| h.undo.pop()) else {
+            return false;
+        };
+        let end = change.start + change.inserted.len();
+        self.splice(Span::new(change.start, end), &change.removed);
+        self.position = change.before;
+        if let Some(history) = &mut self.history {
+            history.redo.push(change);
+        }
+        true
+    }
+
+    /// Makes the last undone edit again, putting the cursor where the edit
+    /// left it. Returns whether there was one; any new edit forgets the
+    /// edits there were to redo.
+    pub fn redo(&mut self) -> bool {
+        let Some(change) = self.history.as_mut().and_then(|h| h.redo.pop()) else {
+            return false;
+        };
+        let end = change.start + change.removed.len();
+        self.splice(Span::new(change.start, end), &change.inserted);
+        self.position = change.after;
+        if let Some(history) = &mut self.history {
+            history.undo.push(change);
+        }
+        true
+    }
+
+    /// [`CursorMut::replace_range`] without recording the edit.
+    fn splice(&mut self, span: Span, s: &str) -> String {
+        let removed = self.text.slice(span.start..span.end).into_owned();
+        let offset = self.offset();
+        if offset < span.start {
+            self.text.replace_range(span.start..span.end, s);
+            return removed;
+        }
+        // Nothing before the line holding the char before the edit can
+        // move, not even its line: a `\r` there might stop or start ending
+        // a line.
+        let before = span.start - self.offset_before_from(span.start, 1).0;
+        let anchor = line_start(&self.text, span.start - before, self.terminator);
+        self.position = self.walk_to(anchor);
+        self.text.replace_range(span.start..span.end, s);
+        self.position = self.walk_to(Edit::new(span, s.len()).map_offset(offset));
+        removed
+    }
+
+    fn window<'t>(&self, text: &'t str) -> Cursor<'t> {
+        let mut cursor = Cursor::new(text);
+        cursor.terminator = self.terminator;
+        cursor.column_unit = self.column_unit;
+        cursor
+    }
+
+    /// The position of the char boundary `target`, reading only the text
+    /// between it and the cursor and back to the start of its line.
+    fn walk_to(&self, target: usize) -> Position {
+        let here = self.position;
+        if target >= here.byte {
+            // The char after `target` decides whether a `\r` before it ends
+            // a line.
+            let end = next_boundary(&self.text, target);
+            let window = self.text.slice(here.byte..end);
+            let mut cursor = self.window(&window);
+            cursor.advance_to(target - here.byte);
+            let column = match cursor.line {
+                0 => here.column + cursor.column,
+                _ => cursor.column,
+            };
+            return Position {
+                byte: target,
+                char_idx: here.char_idx + cursor.char_idx,
+                line: here.line + cursor.line,
+                column,
+            };
+        }
+        let start = line_start(&self.text, target, self.terminator);
+        let window = self.text.slice(start..next_boundary(&self.text, here.byte));
+        let mut cursor = self.window(&window);
+        cursor.advance_to(here.byte - start);
+        let (line, char_idx) = (cursor.line, cursor.char_idx);
+        cursor.move_to(target - start);
+        Position {
+            byte: target,
+            char_idx: here.char_idx - (char_idx - cursor.char_idx),
+            line: here.line - (line - cursor.line),
+            column: cursor.column,
+        }
+    }
+
+    /// The offset `n` chars after the cursor, or the end, and how many
+    /// chars that is.
+    fn offset_after(&self, n: usize) -> (usize, usize) {
+        let start = self.offset();
+        // `n` chars take at most `4 * n` bytes.
+        let mut end = start
+            .saturating_add(n.saturating_mul(4))
+            .min(self.text.len());
+        while !self.text.is_char_boundary(end) {
+            end -= 1;
+        }
+        let window = self.text.slice(start..end);
+        let chars = window.char_indices().take(n);
+        let (moved, last) = chars.fold((0, 0), |(moved, _), (i, c)| (moved + 1, i + c.len_utf8()));
+        (start + last, moved)
+    }
+
+    /// The offset `n` chars before the cursor, or the start, and how many
+    /// chars that is.
+    fn offset_before(&self, n: usize) -> (usize, usize) {
+        self.offset_before_from(self.offset(), n)
+    }
+
+    fn offset_before_from(&self, end: usize, n: usize) -> (usize, usize) {
+        let start = ceil_boundary(&self.text, end.saturating_sub(n.saturating_mul(4)));
+        let window = self.text.slice(start..end);
+        let chars = window.char_indices().rev().take(n);
+        let (moved, first) = chars.fold((0, end - start), |(moved, _), (i, _)| (moved + 1, i));
+        (start + first, moved)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::XorShift;
+    use crate::{GapBuffer, PieceTable};
+
+    fn line_col<S: TextBuffer>(cursor: &CursorMut<S>) -> (usize, usize) {
+        (cursor.line(), cursor.column())
+    }
+
+    #[test]
+    fn test_edit_at_cursor() {
+        let mut cursor = CursorMut::new(String::from("ab\ncd"));
+        cursor.advance_by(4);
+        cursor.insert_str("x\ny");
+        assert_eq!(cursor.text(), "ab\ncx\nyd");
+        assert_eq!((cursor.offset(), line_col(&cursor)), (7, (2, 1)));
+        assert_eq!(cursor.delete_backward(3), "x\ny");
+        assert_eq!((cursor.offset(), line_col(&cursor)), (4, (1, 1)));
+        assert_eq!(cursor.delete_forward(5), "d");
+        cursor.retreat_by(2);
+        assert_eq!(cursor.delete_forward(1), "\n");
+        assert_eq!(line_col(&cursor), (0, 2));
+        assert_eq!(cursor.text(), "abc");
+        assert_eq!(cursor.cursor().remaining(), "c");
+    }
+
+    #[test]
+    fn test_replace_range_and_borrowed_text() {
+        let mut text = String::from("one two three");
+        {
+            let mut cursor = CursorMut::new(&mut text);
+            cursor.seek_to_offset(8).unwrap();
+            assert_eq!(cursor.replace_range(0..3, "1\n"), "one");
+            assert_eq!((cursor.offset(), line_col(&cursor)), (7, (1, 5)));
+            cursor.replace_range(3..9, "");
+            assert_eq!((cursor.offset(), cursor.cursor().remaining()), (3, "ree"));
+        }
+        assert_eq!(text, "1\n ree");
+    }
+
+    #[test]
+    fn test_crlf_split_by_edits() {
+        let mut cursor =
+            CursorMut::new(String::from("a\r\nb")).with_line_terminator(LineTerminator::Ascii);
+        cursor.seek_to_offset(3).unwrap();
+        assert_eq!(line_col(&cursor), (1, 0));
+        cursor.replace_range(2..2, "x");
+        assert_eq!(line_col(&cursor), (2, 0));
+        cursor.replace_range(2..3, "");
+        assert_eq!(line_col(&cursor), (1, 0));
+    }
+
+    #[test]
+    fn test_undo_redo() {
+        let mut cursor = CursorMut::new(String::from("ab\ncd")).with_undo();
+        cursor.advance_by(4);
+        cursor.insert_str("X\n");
+        cursor.delete_backward(3);
+        assert_eq!(cursor.text(), "ab\nd");
+        assert_eq!((cursor.offset(), line_col(&cursor)), (3, (1, 0)));
+
+        assert!(cursor.undo());
+        assert_eq!(cursor.text(), "ab\ncX\nd");
+        assert_eq!((cursor.offset(), line_col(&cursor)), (6, (2, 0)));
+        assert!(cursor.undo());
+        assert_eq!(cursor.text(), "ab\ncd");
+        assert_eq!((cursor.offset(), line_col(&cursor)), (4, (1, 1)));
+        assert!(!cursor.undo());
+
+        assert!(cursor.redo());
+        assert_eq!(cursor.offset(), 6);
+        assert_eq!(cursor.text(), "ab\ncX\nd");
+        cursor.retreat_by(6);
+        cursor.insert_str("!");
+        assert!(!cursor.redo());
+        assert!(cursor.undo());
+        assert_eq!(cursor.offset(), 0);
+        assert_eq!(cursor.text(), "ab\ncX\nd");
+
+        let mut plain = CursorMut::new(String::from("a"));
+        plain.insert_str("b");
+        assert!(!plain.undo());
+    }
+
+    #[test]
+    fn test_long_lines() {
+        let mut cursor = CursorMut::new(GapBuffer::new("a".repeat(1000) + "\nb"));
+        cursor.seek_to_offset(1002).unwrap();
+        assert_eq!(line_col(&cursor), (1, 1));
+        cursor.retreat_by(3);
+        assert_eq!(line_col(&cursor), (0, 999));
+        cursor.seek_to_offset(500).unwrap();
+        cursor.insert_str("\n");
+        assert_eq!(line_col(&cursor), (1, 0));
+        cursor.delete_backward(1);
+        assert_eq!(line_col(&cursor), (0, 500));
+    }
+
+    fn random_edits<S: TextBuffer>(seed: u64, new: impl Fn(String) -> S) {
+        let mut rng = XorShift(seed);
+        let alphabet = ['a', '\u{e9}', '\n', '\r'];
+        for _ in 0..500 {
+            let text = new(rng.string(&alphabet, 0, 10));
+            let mut cursor = CursorMut::new(text).with_line_terminator(LineTerminator::Ascii);
+            for _ in 0..10 {
+                let n = rng.next() as usize % 4;
+                match rng.next() % 5 {
+                    0 => cursor.insert_str(&rng.string(&alphabet, 0, 4)),
+                    1 => drop(cursor.delete_forward(n)),
+                    2 => drop(cursor.delete_backward(n)),
+                    3 => drop(cursor.advance_by(n)),
+                    _ => drop(cursor.retreat_by(n)),
+                }
+            }
+            // Checking only at the end leaves storage like a gap buffer in
+            // whatever state the edits left it.
+            let (offset, position) = (cursor.offset(), cursor.position());
+            let fresh = Cursor::new_at(cursor.text(), offset)
+                .unwrap()
+                .with_line_terminator(LineTerminator::Ascii);
+            assert_eq!(position, Cursor::position(&fresh));
+        }
+    }
+
+    #[test]
+    fn test_random_edits_keep_position() {
+        random_edits(5, |text| text);
+        random_edits(6, GapBuffer::new);
+        random_edits(7, PieceTable::new);
+    }
+}
@@ -0,0 +1,273 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use crate::Cursor;
+
+/// A handler invoked by [`Dispatch`] with the cursor still positioned on the
+/// char that selected it.
+pub type Handler<'a, K> = fn(&mut Cursor<'a>) -> K;
+
+type ClassEntry<'a, K> = (fn(char) -> bool, Handler<'a, K>);
+
+/// A first-char dispatch table: a 128-entry table for ASCII, then explicit
+/// non-ASCII ranges, then non-ASCII char classes in registration order, then
+/// the default handler.
+pub struct Dispatch<'a, K> {
+    ascii: [Handler<'a, K>; 128],
+    ranges: Vec<(char, char, Handler<'a, K>)>,
+    classes: Vec<ClassEntry<'a, K>>,
+    default: Handler<'a, K>,
+}
+
+/// Builds a [`Dispatch`], rejecting chars registered more than once.
+pub struct DispatchBuilder<'a, K> {
+    ascii: [Option<Handler<'a, K>>; 128],
+    ranges: Vec<(char, char, Handler<'a, K>)>,
+    classes: Vec<ClassEntry<'a, K>>,
+    default: Handler<'a, K>,
+    overlap: Option<char>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DispatchError {
+    /// `char` was covered by more than one registration.
+    Overlap(char),
+}
+
+impl fmt::Display for DispatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Overlap(c) => write!(f, "{c:?} has more than one handler"),
+        }
+    }
+}
+
+impl std::error::Error for DispatchError {}
+
+impl<'a, K> Dispatch<'a, K> {
+    /// Starts a table where chars without a handler go to `default`.
+    pub fn builder(default: Handler<'a, K>) -> DispatchBuilder<'a, K> {
+        DispatchBuilder {
+            ascii: [None; 128],
+            ranges: Vec::new(),
+            classes: Vec::new(),
+            default,
+            overlap: None,
+        }
+    }
+
+    /// Returns the handler for `c`.
+    pub fn handler(&self, c: char) -> Handler<'a, K> {
+        if c.is_ascii() {
+            return self.ascii[c as usize];
+        }
+        let idx = self.ranges.partition_point(|&(_, hi, _)| hi < c);
+        if let Some(&(lo, _, handler)) = self.ranges.get(idx) {
+            if lo <= c {
+                return handler;
+            }
+        }
+        self.classes
+            .iter()
+            .find(|(class, _)| class(c))
+            .map_or(self.default, |&(_, handler)| handler)
+    }
+
+    /// Peeks the next char and runs its handler, or returns `None` at the end
+    /// of the input.
+    pub fn dispatch(&self, cursor: &mut Cursor<'a>) -> Option<K> {
+        let c = cursor.peek_char()?;
+        Some(self.handler(c)(cursor))
+    }
+}
+
+impl<'a, K> DispatchBuilder<'a, K> {
+    pub fn char(self, c: char, handler: Handler<'a, K>) -> Self {
+        self.range(c..=c, handler)
+    }
+
+    pub fn chars(mut self, chars: &str, handler: Handler<'a, K>) -> Self {
+        for c in chars.chars() {
+            self = self.char(c, handler);
+        }
+        self
+    }
+
+    pub fn range(mut self, range: RangeInclusive<char>, handler: Handler<'a, K>) -> Self {
+        let (lo, hi) = (*range.start(), *range.end());
+        for c in lo..=hi.min('\x7f') {
+            self.set_ascii(c, handler);
+        }
+        if hi > '\x7f' {
+            let lo = lo.max('\u{80}');
+            if let Some(&(a, _, _)) = self.ranges.iter().find(|&&(a, b, _)| a <= hi && lo <= b) {
+                self.overlap.get_or_insert(a.max(lo));
+            }
+            self.ranges.push((lo, hi, handler));
+        }
+        self
+    }
+
+    /// Registers a char class. ASCII members are checked for overlaps; for
+    /// non-ASCII chars classes are consulted after explicit ranges, in
+    /// registration order.
+    pub fn class(mut self, class: fn(char) -> bool, handler: Handler<'a, K>) -> Self {
+        for c in (0..128u8).map(char::from).filter(|&c| class(c)) {
+            self.set_ascii(c, handler);
+        }
+        self.classes.push((class, handler));
+        self
+    }
+
+    fn set_ascii(&mut self, c: char, handler: Handler<'a, K>) {
+        let slot = &mut self.ascii[c as usize];
+        if slot.is_some() {
+            self.overlap.get_or_insert(c);
+        }
+        *slot = Some(handler);
+    }
+
+    pub fn build(self) -> Result<Dispatch<'a, K>, DispatchError> {
+        if let Some(c) = self.overlap {
+            return Err(DispatchError::Overlap(c));
+        }
+        let mut ranges = self.ranges;
+        ranges.sort_by_key(|&(lo, ..)| lo);
+        Ok(Dispatch {
+            ascii: self.ascii.map(|slot| slot.unwrap_or(self.default)),
+            ranges,
+            classes: self.classes,
+            default: self.default,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum Tok {
+        Ident,
+        Number,
+        Punct,
+        Space,
+        Greek,
+        Unknown,
+    }
+
+    fn run<'a>(cursor: &mut Cursor<'a>, pred: fn(char) -> bool, tok: Tok) -> Tok {
+        while cursor.peek_char().is_some_and(pred) {
+            cursor.next();
+        }
+        tok
+    }
+
+    fn ident(cursor: &mut Cursor) -> Tok {
+        run(cursor, |c| c.is_alphanumeric() || c == '_', Tok::Ident)
+    }
+
+    fn number(cursor: &mut Cursor) -> Tok {
+        run(cursor, |c| c.is_ascii_digit(), Tok::Number)
+    }
+
+    fn space(cursor: &mut Cursor) -> Tok {
+        run(cursor, char::is_whitespace, Tok::Space)
+    }
+
+    fn single(cursor: &mut Cursor, tok: Tok) -> Tok {
+        cursor.next();
+        tok
+    }
+
+    fn punct(cursor: &mut Cursor) -> Tok {
+        single(cursor, Tok::Punct)
+    }
+
+    fn greek(cursor: &mut Cursor) -> Tok {
+        single(cursor, Tok::Greek)
+    }
+
+    fn unknown(cursor: &mut Cursor) -> Tok {
+        single(cursor, Tok::Unknown)
+    }
+
+    fn table<'a>() -> Dispatch<'a, Tok> {
+        Dispatch::builder(unknown)
+            .range('a'..='z', ident)
+            .range('A'..='Z', ident)
+            .char('_', ident)
+            .range('0'..='9', number)
+            .chars("+-*/(){};", punct)
+            .class(char::is_whitespace, space)
+            .range('α'..='ω', greek)
+            .class(|c| !c.is_ascii() && c.is_alphabetic(), ident)
+            .build()
+            .unwrap()
+    }
+
+    fn naive(cursor: &mut Cursor) -> Option<Tok> {
+        let tok = match cursor.peek_char()? {
+            'a'..='z' | 'A'..='Z' | '_' => ident(cursor),
+            '0'..='9' => number(cursor),
+            '+' | '-' | '*' | '/' | '(' | ')' | '{' | '}' | ';' => punct(cursor),
+            c if c.is_whitespace() => space(cursor),
+            'α'..='ω' => greek(cursor),
+            c if c.is_alphabetic() => ident(cursor),
+            _ => unknown(cursor),
+        };
+        Some(tok)
+    }
+
+    #[test]
+    fn test_dispatch_tokens() {
+        let table = table();
+        let mut cursor = Cursor::new("let x1 = (42); λ é?");
+        let toks: Vec<_> = std::iter::from_fn(|| table.dispatch(&mut cursor)).collect();
+        use Tok::*;
+        assert_eq!(
+            toks,
+            [
+                Ident, Space, Ident, Space, Unknown, Space, Punct, Number, Punct, Punct, Space,
+                Greek, Space, Ident, Unknown
+            ]
+        );
+        assert_eq!(table.dispatch(&mut cursor), None);
+    }
+
+    #[test]
+    fn test_dispatch_matches_naive_chain() {
+        let table = table();
+        let input = "fn main() { let αβ = 10 * x_1; } // ünïcödé ✓\n".repeat(50);
+        let (mut a, mut b) = (Cursor::new(&input), Cursor::new(&input));
+        loop {
+            let (x, y) = (table.dispatch(&mut a), naive(&mut b));
+            assert_eq!(x, y);
+            assert_eq!(a.peek(), b.peek());
+            if x.is_none() {
+                break;
+            }
+        }
+    }
+
+    #[test]
+    fn test_overlap_detection() {
+        let err = Dispatch::builder(unknown)
+            .range('a'..='z', ident)
+            .char('q', punct)
+            .build();
+        assert_eq!(err.err(), Some(DispatchError::Overlap('q')));
+
+        let err = Dispatch::builder(unknown)
+            .class(|c| c.is_ascii_digit(), number)
+            .range('5'..='6', punct)
+            .build();
+        assert_eq!(err.err(), Some(DispatchError::Overlap('5')));
+
+        let err = Dispatch::builder(unknown)
+            .range('α'..='ω', greek)
+            .char('λ', ident)
+            .build();
+        assert_eq!(err.err(), Some(DispatchError::Overlap('λ')));
+    }
+}
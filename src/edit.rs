@@ -0,0 +1,380 @@
+use std::fmt;
+
+use crate::{Checkpoint, Cursor, Span};
+
+/// The replacement of the text in `span` by `len` bytes of new text, for
+/// carrying offsets and spans recorded before the edit over to the text
+/// after it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Edit {
+    pub span: Span,
+    pub len: usize,
+}
+
+/// What [`Edit::map_span`] does with a span that overlaps the edit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum OverlapPolicy {
+    /// Trims the span to the text the edit left alone, keeping the new text
+    /// only if the span covered the whole of the old. A span inside the
+    /// edit becomes empty.
+    #[default]
+    Clamp,
+    /// Drops the span.
+    Invalidate,
+}
+
+impl Edit {
+    pub fn new(span: impl Into<Span>, len: usize) -> Self {
+        Self {
+            span: span.into(),
+            len,
+        }
+    }
+
+    /// Where `offset` ends up after the edit. Offsets before the edit stay
+    /// put and offsets after it shift; offsets inside it move to its start.
+    /// Text inserted at an offset goes before it.
+    pub fn map_offset(&self, offset: usize) -> usize {
+        if offset >= self.span.end {
+            offset - self.span.len() + self.len
+        } else {
+            offset.min(self.span.start)
+        }
+    }
+
+    /// Where `span` ends up after the edit, or `None` if it overlaps the
+    /// edit under [`OverlapPolicy::Invalidate`]. Spans that only touch the
+    /// edit just shift, as do their ends by [`Edit::map_offset`], so text
+    /// inserted at the end of a span joins it.
+    pub fn map_span(&self, span: Span, policy: OverlapPolicy) -> Option<Span> {
+        let Span { start, end } = self.span;
+        let inside = |offset| start < offset && offset < end;
+        let overlaps = span.start < end && start < span.end;
+        if overlaps && policy == OverlapPolicy::Invalidate {
+            return None;
+        }
+        let new_start = if inside(span.start) {
+            start + self.len
+        } else {
+            self.map_offset(span.start)
+        };
+        let new_end = if inside(span.end) {
+            start
+        } else {
+            self.map_offset(span.end)
+        };
+        if new_start > new_end {
+            return Some(Span::new(start, start));
+        }
+        Some(Span::new(new_start, new_end))
+    }
+}
+
+impl Cursor<'_> {
+    /// Follows the cursor's input through `edit`, returning a cursor over
+    /// `text`, the input after it. The cursor's position, its marks and
+    /// `checkpoints` move by [`Edit::map_offset`].
+    pub fn with_edit<'b>(
+        &self,
+        text: &'b str,
+        edit: Edit,
+        checkpoints: &mut [Checkpoint],
+    ) -> Cursor<'b> {
+        debug_assert_eq!(
+            text.len(),
+            self.data.len() - edit.span.len() + edit.len,
+            "`edit` doesn't produce `text`"
+        );
+        self.remapped(text, |offset| edit.map_offset(offset), checkpoints)
+    }
+
+    /// A cursor over `text` with the same settings, at `map` of this
+    /// cursor's offset, with the marks and `checkpoints` mapped the same way.
+    /// The line index, if any, is rebuilt for `text`.
+    pub(crate) fn remapped<'b>(
+        &self,
+        text: &'b str,
+        map: impl Fn(usize) -> usize,
+        checkpoints: &mut [Checkpoint],
+    ) -> Cursor<'b> {
+        let mut cursor = Cursor::new(text)
+            .with_line_terminator(self.terminator)
+            .with_column_unit(self.column_unit);
+        cursor.splitter = self.splitter.clone();
+        cursor.trivia = self.trivia.clone();
+        #[cfg(feature = "unicode-normalization")]
+        {
+            cursor.nfc = self.nfc;
+        }
+        if self.line_index.is_some() {
+            cursor = cursor.with_line_index();
+        }
+        let map_to = |cursor: &mut Cursor, offset| {
+            let mut offset = map(offset).min(text.len());
+            while !text.is_char_boundary(offset) {
+                offset -= 1;
+            }
+            cursor.move_to(offset);
+            cursor.checkpoint()
+        };
+        for checkpoint in checkpoints {
+            *checkpoint = map_to(&mut cursor, checkpoint.offset());
+        }
+        let marks: Vec<_> = self
+            .marks
+            .iter()
+            .map(|(name, mark)| (name.clone(), map_to(&mut cursor, mark.offset())))
+            .collect();
+        cursor.marks = marks;
+        let here = map_to(&mut cursor, self.offset);
+        cursor.restore(here);
+        cursor
+    }
+}
+
+/// Replacements recorded while scanning, applied together by
+/// [`EditBuilder::apply`]. All spans refer to the original text, whatever
+/// order the edits were recorded in.
+#[derive(Debug, Clone, Default)]
+pub struct EditBuilder {
+    edits: Vec<(Span, String)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditError {
+    /// Two edits change the same text, or one inserts inside the other.
+    Overlap { first: Span, second: Span },
+    /// The span lies past the end of the text.
+    OutOfRange { span: Span, len: usize },
+    /// The offset falls inside a multibyte char.
+    NotABoundary { offset: usize },
+}
+
+impl fmt::Display for EditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Overlap { first, second } => write!(
+                f,
+                "edits at {}..{} and {}..{} overlap",
+                first.start, first.end, second.start, second.end
+            ),
+            Self::OutOfRange { span, len } => write!(
+                f,
+                "edit at {}..{} is past the end of the text ({len} bytes)",
+                span.start, span.end
+            ),
+            Self::NotABoundary { offset } => write!(f, "offset {offset} is not a char boundary"),
+        }
+    }
+}
+
+impl std::error::Error for EditError {}
+
+impl EditBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the text in `span` with `text`.
+    pub fn replace(&mut self, span: impl Into<Span>, text: impl Into<String>) -> &mut Self {
+        self.edits.push((span.into(), text.into()));
+        self
+    }
+
+    /// Inserts `text` at `offset`. Insertions at the same offset keep the
+    /// order they were recorded in, ahead of any replacement starting
+    /// there.
+    pub fn insert(&mut self, offset: usize, text: impl Into<String>) -> &mut Self {
+        self.replace(offset..offset, text)
+    }
+
+    pub fn delete(&mut self, span: impl Into<Span>) -> &mut Self {
+        self.replace(span, "")
+    }
+
+    pub fn len(&self) -> usize {
+        self.edits.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edits.is_empty()
+    }
+
+    /// The edits in the order [`EditBuilder::apply`] makes them, each as an
+    /// [`Edit`] on the text the ones before it left, for carrying spans
+    /// over to the result.
+    pub fn edits(&self) -> Vec<Edit> {
+        let mut shift = 0isize;
+        let edits = self.sorted().into_iter().map(|(span, text)| {
+            let start = span.start.saturating_add_signed(shift);
+            shift += text.len() as isize - span.len() as isize;
+            Edit::new(start..start + span.len(), text.len())
+        });
+        edits.collect()
+    }
+
+    fn sorted(&self) -> Vec<&(Span, String)> {
+        let mut edits: Vec<_> = self.edits.iter().collect();
+        edits.sort_by_key(|(span, _)| (span.start, span.end));
+        edits
+    }
+
+    /// Applies every edit to `text`, the text the spans refer to. Fails
+    /// without applying any if two edits overlap or a span doesn't fit
+    /// `text`.
+    pub fn apply(&self, text: &str) -> Result<String, EditError> {
+        let edits = self.sorted();
+        let mut end = 0;
+        let mut last: Option<Span> = None;
+        for &&(span, _) in &edits {
+            if span.end > text.len() || span.start > span.end {
+                let len = text.len();
+                return Err(EditError::OutOfRange { span, len });
+            }
+            if let Some(offset) = [span.start, span.end]
+                .into_iter()
+                .find(|&offset| !text.is_char_boundary(offset))
+            {
+                return Err(EditError::NotABoundary { offset });
+            }
+            if let Some(first) = last.filter(|_| span.start < end) {
+                return Err(EditError::Overlap {
+                    first,
+                    second: span,
+                });
+            }
+            end = end.max(span.end);
+            last = Some(span);
+        }
+        let grown = edits.iter().map(|(_, new)| new.len()).sum::<usize>();
+        let mut out = String::with_capacity(text.len() + grown);
+        let mut at = 0;
+        for (span, new) in edits {
+            out.push_str(&text[at..span.start]);
+            out.push_str(new);
+            at = span.end;
+        }
+        out.push_str(&text[at..]);
+        Ok(out)
+    }
+}
+
+impl Cursor<'_> {
+    /// Applies `edits` to the cursor's input; see [`EditBuilder::apply`].
+    pub fn apply_edits(&self, edits: &EditBuilder) -> Result<String, EditError> {
+        edits.apply(self.data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_map_offset() {
+        let edit = Edit::new(4..8, 2);
+        let mapped = [0, 4, 5, 8, 10].map(|offset| edit.map_offset(offset));
+        assert_eq!(mapped, [0, 4, 4, 6, 8]);
+        let insert = Edit::new(3..3, 5);
+        assert_eq!([2, 3, 4].map(|offset| insert.map_offset(offset)), [2, 8, 9]);
+    }
+
+    #[test]
+    fn test_map_span() {
+        use OverlapPolicy::*;
+        let edit = Edit::new(4..8, 2);
+        let map = |start, end, policy| edit.map_span(Span::new(start, end), policy);
+        assert_eq!(map(0, 4, Invalidate), Some(Span::new(0, 4)));
+        assert_eq!(map(8, 9, Invalidate), Some(Span::new(6, 7)));
+        assert_eq!(map(0, 5, Invalidate), None);
+        assert_eq!(map(0, 5, Clamp), Some(Span::new(0, 4)));
+        assert_eq!(map(6, 10, Clamp), Some(Span::new(6, 8)));
+        assert_eq!(map(2, 9, Clamp), Some(Span::new(2, 7)));
+        assert_eq!(map(4, 8, Clamp), Some(Span::new(4, 6)));
+        assert_eq!(map(5, 7, Clamp), Some(Span::new(4, 4)));
+        let insert = Edit::new(3..3, 1);
+        assert_eq!(insert.map_span(Span::new(1, 5), Invalidate), None);
+        assert_eq!(
+            insert.map_span(Span::new(1, 3), Invalidate),
+            Some(Span::new(1, 4))
+        );
+    }
+
+    #[test]
+    fn test_with_edit() {
+        let mut cursor = Cursor::new("let x = 10;\nx");
+        cursor.take_until("1");
+        let value = cursor.checkpoint();
+        cursor.take_until("\n");
+        cursor.set_mark("end");
+        let mut checkpoints = [value];
+        let edit = Edit::new(4..5, 4);
+        let mut cursor = cursor.with_edit("let name = 10;\nx", edit, &mut checkpoints);
+        assert_eq!((cursor.offset(), cursor.column()), (14, 14));
+        cursor.restore(checkpoints[0]);
+        assert_eq!(cursor.remaining(), "10;\nx");
+        assert!(cursor.goto_mark("end"));
+        assert_eq!(cursor.offset(), 14);
+    }
+
+    #[test]
+    fn test_edit_builder() {
+        let text = "let x = 1; let y = x;";
+        let mut cursor = Cursor::new(text);
+        let mut edits = EditBuilder::new();
+        while let Some((start, word)) = cursor.next_word() {
+            match word {
+                "let" => drop(edits.replace(start..start + 3, "const")),
+                "x" | "x;" => drop(edits.replace(start..start + 1, "X")),
+                _ => {}
+            }
+        }
+        edits.insert(0, "// fixed\n").insert(0, "\n").delete(9..10);
+        assert_eq!(edits.len(), 7);
+        let fixed = cursor.apply_edits(&edits).unwrap();
+        assert_eq!(fixed, "// fixed\n\nconst X = 1 const y = X;");
+
+        let span = Span::new(15, 16);
+        let edits = edits.edits();
+        let moved = edits.iter().fold(span, |span, edit| {
+            edit.map_span(span, OverlapPolicy::Clamp).unwrap()
+        });
+        assert_eq!(&fixed[moved.start..moved.end], "y");
+    }
+
+    #[test]
+    fn test_edit_builder_errors() {
+        let mut edits = EditBuilder::new();
+        edits.replace(4..6, "a").replace(2..5, "b");
+        let err = edits.apply("abcdefg").unwrap_err();
+        let (first, second) = (Span::new(2, 5), Span::new(4, 6));
+        assert_eq!(err, EditError::Overlap { first, second });
+        assert_eq!(err.to_string(), "edits at 2..5 and 4..6 overlap");
+
+        let mut edits = EditBuilder::new();
+        edits.replace(2..5, "").insert(3, "x");
+        assert!(matches!(
+            edits.apply("abcdefg"),
+            Err(EditError::Overlap { .. })
+        ));
+        let mut edits = EditBuilder::new();
+        edits.replace(2..5, "").insert(5, "x").insert(2, "y");
+        assert_eq!(edits.apply("abcdefg").unwrap(), "abyxfg");
+
+        let mut edits = EditBuilder::new();
+        edits.insert(9, "x");
+        assert_eq!(
+            edits.apply("abc"),
+            Err(EditError::OutOfRange {
+                span: Span::new(9, 9),
+                len: 3
+            })
+        );
+        let mut edits = EditBuilder::new();
+        edits.insert(1, "x");
+        assert_eq!(
+            edits.apply("\u{e9}"),
+            Err(EditError::NotABoundary { offset: 1 })
+        );
+    }
+}
@@ -0,0 +1,449 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use crate::{ColumnUnit, Cursor, LineTerminator, Position, Recoverable};
+
+/// A parse failure at a position in the input, with the stack of constructs
+/// that were being parsed when it happened.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    message: Cow<'static, str>,
+    offset: usize,
+    line: usize,
+    column: usize,
+    context: Vec<ContextFrame>,
+    suggestion: Option<Cow<'static, str>>,
+    fatal: bool,
+    terminator: LineTerminator,
+    column_unit: ColumnUnit,
+}
+
+/// A construct that was being parsed when an error occurred, and where it
+/// started.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextFrame {
+    pub label: Cow<'static, str>,
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl ParseError {
+    /// Creates an error at the given byte offset and zero-based line and
+    /// column.
+    pub fn new(
+        message: impl Into<Cow<'static, str>>,
+        offset: usize,
+        line: usize,
+        column: usize,
+    ) -> Self {
+        Self {
+            message: message.into(),
+            offset,
+            line,
+            column,
+            context: Vec::new(),
+            suggestion: None,
+            fatal: false,
+            terminator: LineTerminator::default(),
+            column_unit: ColumnUnit::default(),
+        }
+    }
+
+    /// Says where the lines of the source end, for [`ParseError::render`].
+    /// Errors from [`Cursor::error`] take the cursor's.
+    pub fn with_line_terminator(mut self, terminator: LineTerminator) -> Self {
+        self.terminator = terminator;
+        self
+    }
+
+    /// Says what the columns count, for [`ParseError::render`]. Errors from
+    /// [`Cursor::error`] take the cursor's.
+    pub fn with_column_unit(mut self, unit: ColumnUnit) -> Self {
+        self.column_unit = unit;
+        self
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub const fn line(&self) -> usize {
+        self.line
+    }
+
+    pub const fn column(&self) -> usize {
+        self.column
+    }
+
+    /// Whether the error aborts backtracking; see [`Recoverable`].
+    pub const fn is_fatal(&self) -> bool {
+        self.fatal
+    }
+
+    pub fn into_fatal(mut self) -> Self {
+        self.fatal = true;
+        self
+    }
+
+    /// The enclosing constructs, innermost first.
+    pub fn context(&self) -> &[ContextFrame] {
+        &self.context
+    }
+
+    /// Wraps the error in another enclosing construct.
+    pub fn push_context(mut self, frame: ContextFrame) -> Self {
+        self.context.push(frame);
+        self
+    }
+
+    /// Attaches a "did you mean" suggestion, such as one from
+    /// [`Cursor::suggest_similar`].
+    pub fn with_suggestion(mut self, suggestion: impl Into<Cow<'static, str>>) -> Self {
+        self.suggestion = Some(suggestion.into());
+        self
+    }
+
+    pub fn suggestion(&self) -> Option<&str> {
+        self.suggestion.as_deref()
+    }
+
+    /// The message followed by the suggestion, if any.
+    fn headline(&self) -> Cow<'_, str> {
+        match &self.suggestion {
+            Some(suggestion) => format!("{}, did you mean `{suggestion}`?", self.message).into(),
+            None => Cow::Borrowed(&self.message),
+        }
+    }
+
+    /// Renders the error with the offending source lines, marking where each
+    /// enclosing construct started and where the failure happened.
+    pub fn render(&self, source: &str) -> String {
+        let mut marks: Vec<(usize, usize, char, &str)> = self
+            .context
+            .iter()
+            .map(|frame| (frame.line, frame.column, '-', &*frame.label))
+            .collect();
+        let headline = self.headline();
+        marks.push((self.line, self.column, '^', &headline));
+        marks.sort_by_key(|&(line, column, ..)| (line, column));
+
+        let mut lines = Vec::new();
+        let mut start = 0;
+        while let Some(next) = self.terminator.nth_after(source, start, 0) {
+            lines.push(self.terminator.strip(&source[start..next]));
+            start = next;
+        }
+        lines.push(&source[start..]);
+        let last = marks.iter().map(|&(line, ..)| line).max().unwrap_or(0);
+        let width = (last + 1).to_string().len();
+        let mut out = format!(
+            "error: {}\n{:width$}--> {}:{}\n",
+            headline,
+            "",
+            self.line + 1,
+            self.column + 1,
+        );
+        let mut last_line = None;
+        for (line, column, marker, label) in marks {
+            let text = lines.get(line).copied().unwrap_or("");
+            if last_line != Some(line) {
+                if last_line.is_some_and(|last| last + 1 < line) {
+                    out.push_str("...\n");
+                }
+                out.push_str(&format!("{:>width$} | {}\n", line + 1, text));
+                last_line = Some(line);
+            }
+            let mut at = 0;
+            let pad: String = text
+                .chars()
+                .take_while(|&c| {
+                    at += self.column_unit.width_of(c);
+                    at <= column
+                })
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect();
+            let label = if marker == '-' {
+                format!("{label} starts here")
+            } else {
+                label.to_string()
+            };
+            out.push_str(&format!("{:width$} | {pad}{marker} {label}\n", ""));
+        }
+        out
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for frame in self.context.iter().rev() {
+            write!(
+                f,
+                "while parsing {} (started at {}:{}): ",
+                frame.label,
+                frame.line + 1,
+                frame.column + 1
+            )?;
+        }
+        write!(
+            f,
+            "{} at {}:{}",
+            self.headline(),
+            self.line + 1,
+            self.column + 1
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl Recoverable for ParseError {
+    fn is_recoverable(&self) -> bool {
+        !self.fatal
+    }
+}
+
+/// A literal that [`Cursor::expect_char`] or [`Cursor::expect_str`] did not
+/// find at the cursor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CursorError {
+    pub expected: String,
+    /// The char at the cursor, or `None` at the end of the input.
+    pub found: Option<char>,
+    pub position: Position,
+    /// The cursor's settings, which [`CursorError::render`] reads the
+    /// position with.
+    pub terminator: LineTerminator,
+    pub column_unit: ColumnUnit,
+}
+
+impl CursorError {
+    /// The description, without the position.
+    pub fn message(&self) -> String {
+        match self.found {
+            Some(c) => format!("expected {:?}, found {c:?}", self.expected),
+            None => format!("expected {:?}, found end of input", self.expected),
+        }
+    }
+
+    /// Renders the error with the offending source line, the way
+    /// [`ParseError::render`] does.
+    pub fn render(&self, source: &str) -> String {
+        ParseError::from(self.clone()).render(source)
+    }
+}
+
+impl fmt::Display for CursorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at {}", self.message(), self.position)
+    }
+}
+
+impl std::error::Error for CursorError {}
+
+impl From<CursorError> for ParseError {
+    fn from(err: CursorError) -> Self {
+        let Position {
+            byte, line, column, ..
+        } = err.position;
+        ParseError::new(err.message(), byte, line, column)
+            .with_line_terminator(err.terminator)
+            .with_column_unit(err.column_unit)
+    }
+}
+
+impl<'a> Cursor<'a> {
+    /// Creates a [`ParseError`] at the cursor's current position.
+    pub fn error(&self, message: impl Into<Cow<'static, str>>) -> ParseError {
+        let column = self.column;
+        ParseError::new(message, self.offset, self.line, column)
+            .with_line_terminator(self.terminator)
+            .with_column_unit(self.column_unit)
+    }
+
+    /// Consumes `c`, or fails without moving if it is not the next char.
+    pub fn expect_char(&mut self, c: char) -> Result<(), CursorError> {
+        if self.eat_char(c) {
+            return Ok(());
+        }
+        Err(self.expected(c.to_string()))
+    }
+
+    /// Consumes `s`, or fails without moving if the remaining input does not
+    /// start with it.
+    pub fn expect_str(&mut self, s: &str) -> Result<(), CursorError> {
+        if self.eat_str(s) {
+            return Ok(());
+        }
+        Err(self.expected(s.to_owned()))
+    }
+
+    fn expected(&self, expected: String) -> CursorError {
+        CursorError {
+            expected,
+            found: self.peek_char(),
+            position: self.position(),
+            terminator: self.terminator,
+            column_unit: self.column_unit,
+        }
+    }
+
+    /// Runs `f`, labelling any error it returns with the construct being
+    /// parsed and where it started.
+    pub fn with_context<T>(
+        &mut self,
+        label: impl Into<Cow<'static, str>>,
+        f: impl FnOnce(&mut Cursor<'a>) -> Result<T, ParseError>,
+    ) -> Result<T, ParseError> {
+        let (offset, line) = (self.offset, self.line);
+        f(self).map_err(|err| {
+            err.push_context(ContextFrame {
+                label: label.into(),
+                offset,
+                line,
+                column: self.column_at(offset),
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "let x = 1;\nlet y =\n  [1,\n   2\n}";
+
+    fn array(cursor: &mut Cursor) -> Result<(), ParseError> {
+        cursor.with_context("array literal", |c| {
+            c.eat_str("[");
+            c.take_until("}");
+            Err(c.error("expected ']'"))
+        })
+    }
+
+    fn statement(cursor: &mut Cursor) -> Result<(), ParseError> {
+        cursor.take_until("let y");
+        cursor.with_context("let statement", |c| {
+            c.take_until("[");
+            array(c)
+        })
+    }
+
+    #[test]
+    fn test_error_display() {
+        let mut cursor = Cursor::new("ab\ncd");
+        cursor.take_until("d");
+        let err = cursor.error("unexpected 'd'");
+        assert_eq!((err.offset(), err.line(), err.column()), (4, 1, 1));
+        assert_eq!(err.to_string(), "unexpected 'd' at 2:2");
+    }
+
+    #[test]
+    fn test_nested_context() {
+        let mut cursor = Cursor::new(SOURCE);
+        let err = statement(&mut cursor).unwrap_err();
+        let labels: Vec<_> = err.context().iter().map(|f| &*f.label).collect();
+        assert_eq!(labels, ["array literal", "let statement"]);
+        assert_eq!(
+            err.to_string(),
+            "while parsing let statement (started at 2:1): \
+             while parsing array literal (started at 3:3): expected ']' at 5:1"
+        );
+    }
+
+    #[test]
+    fn test_context_is_untouched_on_success() {
+        let mut cursor = Cursor::new("x");
+        let value = cursor.with_context("letter", |c| c.next_char().ok_or(c.error("eof")));
+        assert_eq!(value, Ok('x'));
+    }
+
+    #[test]
+    fn test_expect() {
+        let mut cursor = Cursor::new("f(x)\n->");
+        assert_eq!(cursor.expect_char('f'), Ok(()));
+        assert_eq!(cursor.expect_str("(x)"), Ok(()));
+        let err = cursor.expect_char(';').unwrap_err();
+        assert_eq!((err.found, err.position.byte), (Some('\n'), 4));
+        assert_eq!(err.to_string(), "expected \";\", found '\\n' at 1:5");
+
+        cursor.next();
+        assert_eq!(cursor.expect_str("->"), Ok(()));
+        let err = cursor.expect_str("=>").unwrap_err();
+        assert_eq!(err.expected, "=>");
+        assert_eq!(
+            err.to_string(),
+            "expected \"=>\", found end of input at 2:3"
+        );
+        assert_eq!(cursor.offset(), 7);
+    }
+
+    #[test]
+    fn test_cursor_error_render() {
+        let source = "let x = 1;\nlet y = 2\nlet z = 3;";
+        let mut cursor = Cursor::new(source);
+        cursor.take_until("\nlet z");
+        let err = cursor.expect_char(';').unwrap_err();
+        assert_eq!(
+            err.render(source),
+            "error: expected \";\", found '\\n'\n \
+             --> 2:10\n\
+             2 | let y = 2\n  \
+             |          ^ expected \";\", found '\\n'\n"
+        );
+
+        let parsed: Result<(), ParseError> =
+            cursor.with_context("statement", |c| Ok(c.expect_str(";")?));
+        assert_eq!(parsed.unwrap_err().line(), 1);
+    }
+
+    #[test]
+    fn test_render_follows_cursor_settings() {
+        let source = "a\r\nb\u{2028}\u{1f600}x y";
+        let mut cursor = Cursor::new(source)
+            .with_line_terminator(LineTerminator::Unicode)
+            .with_column_unit(ColumnUnit::Utf16);
+        cursor.take_until("y");
+        let err = cursor.error("unexpected 'y'");
+        assert_eq!((err.line(), err.column()), (2, 4));
+        assert_eq!(
+            err.render(source),
+            "error: unexpected 'y'\n \
+             --> 3:5\n\
+             3 | \u{1f600}x y\n  \
+             |    ^ unexpected 'y'\n"
+        );
+
+        let err = cursor.expect_char(';').unwrap_err();
+        assert!(err
+            .render(source)
+            .ends_with("|    ^ expected \";\", found 'y'\n"));
+
+        cursor.seek_to_line(0).unwrap();
+        cursor.next();
+        let rendered = cursor.error("x").render(source);
+        assert!(rendered.contains("1 | a\n"));
+    }
+
+    #[test]
+    fn test_render_marks_start_and_failure() {
+        let mut cursor = Cursor::new(SOURCE);
+        cursor.take_until("[");
+        let err = array(&mut cursor).unwrap_err();
+        assert_eq!(err.context().len(), 1);
+        assert_eq!(
+            err.render(SOURCE),
+            "error: expected ']'\n \
+             --> 5:1\n\
+             3 |   [1,\n  \
+             |   - array literal starts here\n\
+             ...\n\
+             5 | }\n  \
+             | ^ expected ']'\n"
+        );
+    }
+}
@@ -0,0 +1,119 @@
+use crate::Cursor;
+
+/// Iterator over the delimited fields of the current line; see
+/// [`Cursor::fields`].
+pub struct CursorFields<'a, 'b> {
+    cursor: &'b mut Cursor<'a>,
+    delimiter: char,
+    quote: Option<char>,
+    done: bool,
+}
+
+impl CursorFields<'_, '_> {
+    /// Lets fields be wrapped in `quote` to hold delimiters. A quoted field
+    /// yields the text between the quotes, where a doubled quote stands for
+    /// one and is left as it is; anything between the closing quote and the
+    /// next delimiter is skipped.
+    pub fn quote(mut self, quote: char) -> Self {
+        self.quote = Some(quote);
+        self
+    }
+}
+
+impl<'a> Cursor<'a> {
+    /// Iterates over the fields of the current line, from the cursor to the
+    /// line's end, as split by `delimiter`. Each field comes with its start
+    /// offset; after the last one the cursor moves past the line terminator,
+    /// so calling this again reads the next line. An empty line has a single
+    /// empty field.
+    pub fn fields(&mut self, delimiter: char) -> CursorFields<'a, '_> {
+        let done = self.is_at_end();
+        CursorFields {
+            cursor: self,
+            delimiter,
+            quote: None,
+            done,
+        }
+    }
+}
+
+impl<'a> Iterator for CursorFields<'a, '_> {
+    type Item = (usize, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let (data, start) = (self.cursor.data, self.cursor.offset);
+        let terminator = self.cursor.terminator;
+        let next_line = terminator.nth_after(data, start, 0).unwrap_or(data.len());
+        let line = terminator.strip(&data[start..next_line]);
+        // The field, and the rest of the line to look for a delimiter in.
+        let (field, rest) = match self.quote {
+            Some(q) if line.starts_with(q) => {
+                let inner = &line[q.len_utf8()..];
+                let mut chars = inner.char_indices().peekable();
+                let mut end = inner.len();
+                while let Some((i, c)) = chars.next() {
+                    if c == q && chars.next_if(|&(_, c)| c == q).is_none() {
+                        end = i;
+                        break;
+                    }
+                }
+                let after = (end + q.len_utf8()).min(inner.len());
+                ((start + q.len_utf8(), &inner[..end]), &inner[after..])
+            }
+            _ => {
+                let end = line.find(self.delimiter).unwrap_or(line.len());
+                ((start, &line[..end]), &line[end..])
+            }
+        };
+        match rest.find(self.delimiter) {
+            Some(at) => {
+                let consumed = line.len() - rest.len() + at + self.delimiter.len_utf8();
+                self.cursor.advance_to(start + consumed);
+            }
+            None => {
+                self.done = true;
+                self.cursor.advance_to(next_line);
+            }
+        }
+        Some(field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fields() {
+        let mut cursor = Cursor::new("a,b,,c\n\nlast,\n");
+        let fields: Vec<_> = cursor.fields(',').collect();
+        assert_eq!(fields, [(0, "a"), (2, "b"), (4, ""), (5, "c")]);
+        assert_eq!(cursor.offset(), 7);
+        assert_eq!(cursor.fields(',').collect::<Vec<_>>(), [(7, "")]);
+        assert_eq!(
+            cursor.fields(',').collect::<Vec<_>>(),
+            [(8, "last"), (13, "")]
+        );
+        assert!(cursor.is_at_end());
+        assert_eq!(cursor.fields(',').next(), None);
+    }
+
+    #[test]
+    fn test_quoted_fields() {
+        let text = "id,\"x, y\",\"say \"\"hi\"\"\" ,\"open\r\nnext";
+        let mut cursor = Cursor::new(text).with_line_terminator(crate::LineTerminator::Ascii);
+        let fields: Vec<_> = cursor.fields(',').quote('"').collect();
+        assert_eq!(
+            fields,
+            [(0, "id"), (4, "x, y"), (11, "say \"\"hi\"\""), (25, "open")]
+        );
+        assert_eq!(cursor.peek_word(), Some((31, "next")));
+
+        let mut cursor = Cursor::new("a,\"b,c\"");
+        let unquoted: Vec<_> = cursor.fields(',').map(|(_, f)| f).collect();
+        assert_eq!(unquoted, ["a", "\"b", "c\""]);
+    }
+}
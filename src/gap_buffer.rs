@@ -0,0 +1,180 @@
+use std::borrow::Cow;
+use std::ops::Range;
+
+use crate::TextBuffer;
+
+/// The least room [`GapBuffer`] makes when it grows its gap.
+const MIN_GAP: usize = 64;
+
+/// Text with a gap where the last edit happened, as the storage of a
+/// [`CursorMut`](crate::CursorMut). Typing and deleting at the same place
+/// cost only the text involved, and moving the edit point costs only the
+/// text it moves over, rather than everything after the edit.
+#[derive(Debug, Clone, Default)]
+pub struct GapBuffer {
+    /// The text with the gap spliced in. The bytes in the gap are left over
+    /// from earlier edits and never read.
+    buf: Vec<u8>,
+    gap: Range<usize>,
+}
+
+impl GapBuffer {
+    pub fn new(text: impl Into<String>) -> Self {
+        let buf = text.into().into_bytes();
+        let end = buf.len();
+        Self { buf, gap: end..end }
+    }
+
+    pub fn into_string(mut self) -> String {
+        self.move_gap(self.len());
+        self.buf.truncate(self.gap.start);
+        String::from_utf8(self.buf).expect("gap buffer holds UTF-8")
+    }
+
+    fn gap_len(&self) -> usize {
+        self.gap.len()
+    }
+
+    /// The offset in `buf` of the text offset `offset`. The gap's start is
+    /// the text after the gap.
+    fn buf_offset(&self, offset: usize) -> usize {
+        if offset < self.gap.start {
+            offset
+        } else {
+            offset + self.gap_len()
+        }
+    }
+
+    /// The bytes of `buf` in `range`, which lies on one side of the gap.
+    fn str(&self, range: Range<usize>) -> &str {
+        std::str::from_utf8(&self.buf[range]).expect("gap buffer holds UTF-8")
+    }
+
+    /// Moves the gap to the text offset `offset`, moving the text in between
+    /// across it.
+    fn move_gap(&mut self, offset: usize) {
+        let Range { start, end } = self.gap.clone();
+        if offset == start {
+            return;
+        }
+        if offset < start {
+            self.buf.copy_within(offset..start, end - (start - offset));
+        } else {
+            self.buf.copy_within(end..end + offset - start, start);
+        }
+        self.gap = offset..offset + self.gap_len();
+    }
+}
+
+impl TextBuffer for GapBuffer {
+    fn len(&self) -> usize {
+        self.buf.len() - self.gap_len()
+    }
+
+    fn is_char_boundary(&self, offset: usize) -> bool {
+        // Like `str::is_char_boundary`: anything but a continuation byte.
+        offset == self.len()
+            || offset < self.len() && (self.buf[self.buf_offset(offset)] as i8) >= -0x40
+    }
+
+    fn slice(&self, range: Range<usize>) -> Cow<'_, str> {
+        let gap = self.gap.start;
+        if range.end <= gap {
+            return Cow::Borrowed(self.str(range));
+        }
+        let after = self.buf_offset(range.end);
+        if range.start >= gap {
+            return Cow::Borrowed(self.str(self.buf_offset(range.start)..after));
+        }
+        Cow::Owned(self.str(range.start..gap).to_owned() + self.str(self.gap.end..after))
+    }
+
+    fn replace_range(&mut self, range: Range<usize>, s: &str) {
+        assert!(
+            range.start <= range.end && self.is_char_boundary(range.start),
+            "invalid range start {}",
+            range.start
+        );
+        assert!(
+            self.is_char_boundary(range.end),
+            "invalid range end {}",
+            range.end
+        );
+        self.move_gap(range.start);
+        // The replaced text joins the gap.
+        self.gap.end += range.len();
+        if s.len() > self.gap_len() {
+            let grow = s.len() - self.gap_len() + MIN_GAP.max(self.len() / 8);
+            let end = self.gap.end;
+            self.buf.splice(end..end, std::iter::repeat_n(0, grow));
+            self.gap.end += grow;
+        }
+        let start = self.gap.start;
+        self.buf[start..start + s.len()].copy_from_slice(s.as_bytes());
+        self.gap.start += s.len();
+    }
+
+    fn make_contiguous(&mut self) -> &str {
+        self.move_gap(self.len());
+        self.str(0..self.gap.start)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CursorMut;
+
+    #[test]
+    fn test_gap_buffer() {
+        let mut buffer = GapBuffer::new("hello world");
+        buffer.replace_range(5..5, ",");
+        buffer.replace_range(7..12, "th\u{e9}re");
+        assert_eq!(buffer.slice(0..6), "hello,");
+        assert!(!buffer.is_char_boundary(10));
+        buffer.replace_range(0..1, "H");
+        assert_eq!(buffer.slice(0..buffer.len()), "Hello, th\u{e9}re");
+        assert_eq!(buffer.make_contiguous(), "Hello, th\u{e9}re");
+        assert_eq!(buffer.into_string(), "Hello, th\u{e9}re");
+    }
+
+    #[test]
+    fn test_slice_from_gap_start() {
+        let mut buffer = GapBuffer::new("ab\ncd");
+        buffer.replace_range(2..2, "X");
+        assert_eq!(buffer.slice(3..5), "\nc");
+        assert_eq!(buffer.slice(2..6), "X\ncd");
+        assert!(buffer.is_char_boundary(3));
+
+        let mut cursor = CursorMut::new(GapBuffer::new("ab\ncd"));
+        cursor.advance_by(2);
+        cursor.insert_str("X");
+        cursor.advance_by(2);
+        assert_eq!((cursor.line(), cursor.column()), (1, 1));
+        cursor.retreat_by(2);
+        assert_eq!(cursor.delete_forward(2), "\nc");
+
+        let mut cursor = CursorMut::new(GapBuffer::new("ab\u{e9}\u{e9}"));
+        cursor.advance_by(2);
+        cursor.insert_str("X");
+        cursor.advance_by(1);
+        assert_eq!((cursor.offset(), cursor.column()), (5, 4));
+    }
+
+    #[test]
+    fn test_typing_into_gap_buffer() {
+        let mut cursor = CursorMut::new(GapBuffer::new("fn main() {}"));
+        cursor.advance_by(11);
+        for c in "\n    println!();\n".chars() {
+            cursor.insert_str(c.encode_utf8(&mut [0; 4]));
+        }
+        assert_eq!((cursor.line(), cursor.column()), (2, 0));
+        cursor.delete_backward(4);
+        assert_eq!((cursor.line(), cursor.column()), (1, 12));
+        assert_eq!(cursor.cursor().remaining(), "}");
+        assert_eq!(
+            cursor.into_inner().into_string(),
+            "fn main() {\n    println!}"
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::Cursor;
+
+impl<'a> Cursor<'a> {
+    /// Consumes the next extended grapheme cluster, the unit a user sees as
+    /// one character, returning its offset and text.
+    pub fn next_grapheme(&mut self) -> Option<(usize, &'a str)> {
+        let start = self.offset;
+        let grapheme = self.forward().graphemes(true).next()?;
+        self.advance_to(start + grapheme.len());
+        Some((start, grapheme))
+    }
+
+    /// Moves back over the grapheme cluster before the cursor.
+    pub fn prev_grapheme(&mut self) -> Option<(usize, &'a str)> {
+        let grapheme = self.backward().graphemes(true).next_back()?;
+        self.retreat_to(self.offset - grapheme.len());
+        Some((self.offset, grapheme))
+    }
+
+    pub fn graphemes(&mut self) -> CursorGraphemes<'a, '_> {
+        CursorGraphemes { cursor: self }
+    }
+}
+
+/// Iterator over the remaining grapheme clusters; see [`Cursor::graphemes`].
+pub struct CursorGraphemes<'a, 'b> {
+    cursor: &'b mut Cursor<'a>,
+}
+
+impl<'a> Iterator for CursorGraphemes<'a, '_> {
+    type Item = (usize, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cursor.next_grapheme()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FAMILY: &str = "👨\u{200d}👩\u{200d}👧";
+
+    #[test]
+    fn test_graphemes() {
+        let text = format!("e\u{301}{FAMILY}\r\nx");
+        let mut cursor = Cursor::new(&text);
+        let clusters: Vec<_> = cursor.graphemes().map(|(_, g)| g).collect();
+        assert_eq!(clusters, ["e\u{301}", FAMILY, "\r\n", "x"]);
+        assert_eq!((cursor.line(), cursor.column()), (1, 1));
+    }
+
+    #[test]
+    fn test_prev_grapheme() {
+        let text = format!("a{FAMILY}");
+        let mut cursor = Cursor::new_at_end(&text);
+        assert_eq!(cursor.prev_grapheme(), Some((1, FAMILY)));
+        assert_eq!(cursor.column(), 1);
+        assert_eq!(cursor.prev_grapheme(), Some((0, "a")));
+        assert_eq!(cursor.prev_grapheme(), None);
+        assert_eq!(cursor.next_grapheme(), Some((0, "a")));
+    }
+}
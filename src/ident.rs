@@ -0,0 +1,70 @@
+use crate::Cursor;
+
+#[cfg(feature = "xid")]
+fn is_ident_start(c: char) -> bool {
+    c == '_' || unicode_ident::is_xid_start(c)
+}
+
+#[cfg(feature = "xid")]
+pub(crate) fn is_ident_continue(c: char) -> bool {
+    unicode_ident::is_xid_continue(c)
+}
+
+#[cfg(not(feature = "xid"))]
+fn is_ident_start(c: char) -> bool {
+    c == '_' || c.is_ascii_alphabetic()
+}
+
+#[cfg(not(feature = "xid"))]
+pub(crate) fn is_ident_continue(c: char) -> bool {
+    c == '_' || c.is_ascii_alphanumeric()
+}
+
+impl<'a> Cursor<'a> {
+    /// Consumes an identifier at the cursor: an `XID_Start` char or `_`,
+    /// then any `XID_Continue` chars, as in Rust and Python. Without the
+    /// `xid` feature only ASCII letters, digits and `_` count.
+    pub fn next_identifier(&mut self) -> Option<(usize, &'a str)> {
+        let start = self.offset;
+        let rest = self.forward();
+        let first = rest.chars().next().filter(|&c| is_ident_start(c))?;
+        let len = rest[first.len_utf8()..]
+            .find(|c| !is_ident_continue(c))
+            .map_or(rest.len(), |len| first.len_utf8() + len);
+        self.advance_to(start + len);
+        Some((start, &rest[..len]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_identifier() {
+        let mut cursor = Cursor::new("_tmp1 = x2+9y");
+        assert_eq!(cursor.next_identifier(), Some((0, "_tmp1")));
+        assert_eq!(cursor.next_identifier(), None);
+        assert_eq!(cursor.offset(), 5);
+        cursor.advance_by(3);
+        assert_eq!(cursor.next_identifier(), Some((8, "x2")));
+        cursor.next();
+        assert_eq!(cursor.next_identifier(), None);
+    }
+
+    #[cfg(feature = "xid")]
+    #[test]
+    fn test_unicode_identifier() {
+        let mut cursor = Cursor::new("größe·x = ٣");
+        assert_eq!(cursor.next_identifier(), Some((0, "größe·x")));
+        cursor.advance_by(3);
+        assert_eq!(cursor.next_identifier(), None);
+    }
+
+    #[cfg(not(feature = "xid"))]
+    #[test]
+    fn test_ascii_fallback() {
+        let mut cursor = Cursor::new("größe");
+        assert_eq!(cursor.next_identifier(), Some((0, "gr")));
+    }
+}
@@ -0,0 +1,445 @@
+use std::collections::VecDeque;
+
+use crate::{Cursor, LineTerminator};
+
+/// Lines on each side of the cursor that [`Cursor::suggest_indent`] detects
+/// the indent style from.
+const DETECT_LINES: usize = 100;
+
+/// One level of indentation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndentStyle {
+    Tabs,
+    Spaces(usize),
+}
+
+impl IndentStyle {
+    /// Guesses the style from the leading whitespace of the lines in `text`:
+    /// tabs if any line is tab-indented, otherwise the smallest space indent,
+    /// falling back to four spaces.
+    pub fn detect(text: &str) -> Self {
+        Self::detect_lines(text.lines())
+    }
+
+    fn detect_lines<'t>(lines: impl Iterator<Item = &'t str>) -> Self {
+        let mut smallest = None;
+        for line in lines {
+            if line.starts_with('\t') {
+                return Self::Tabs;
+            }
+            let spaces = line.len() - line.trim_start_matches(' ').len();
+            if spaces > 0 && spaces < line.len() {
+                smallest = Some(smallest.map_or(spaces, |s: usize| s.min(spaces)));
+            }
+        }
+        Self::Spaces(smallest.unwrap_or(4))
+    }
+
+    fn unit(self) -> String {
+        match self {
+            Self::Tabs => "\t".to_string(),
+            Self::Spaces(n) => " ".repeat(n),
+        }
+    }
+}
+
+/// Configuration for [`Cursor::suggest_indent`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndentConfig {
+    /// Chars that open a block when they end a line.
+    pub openers: String,
+    /// Chars that close a block when they start the rest of a line.
+    pub closers: String,
+    /// The indentation unit, or `None` to detect it from the lines around
+    /// the cursor.
+    pub style: Option<IndentStyle>,
+}
+
+impl Default for IndentConfig {
+    fn default() -> Self {
+        Self {
+            openers: "{[(:".to_string(),
+            closers: "}])".to_string(),
+            style: None,
+        }
+    }
+}
+
+fn leading_whitespace(line: &str) -> &str {
+    &line[..line.len() - line.trim_start().len()]
+}
+
+/// The lines of `data[start..end]`, which starts at a line start, without
+/// their terminators.
+fn lines_between(
+    data: &str,
+    terminator: LineTerminator,
+    start: usize,
+    end: usize,
+) -> impl Iterator<Item = &str> {
+    let mut at = start;
+    std::iter::from_fn(move || {
+        if at >= end {
+            return None;
+        }
+        let next = terminator.nth_after(data, at, 0).unwrap_or(end).min(end);
+        let line = terminator.strip(&data[at..next]);
+        at = next;
+        Some(line)
+    })
+}
+
+impl Cursor<'_> {
+    /// Suggests the indentation for a new line inserted at the cursor.
+    ///
+    /// Starts from the current line's indentation (or, if the line is blank,
+    /// that of the previous non-blank line), adds a level if the text before
+    /// the cursor ends with an opener, and removes one if the text after it
+    /// starts with a closer. Existing indentation is kept verbatim, tabs
+    /// included.
+    pub fn suggest_indent(&self, config: &IndentConfig) -> String {
+        let (data, terminator) = (self.data, self.terminator);
+        let line_start = terminator.line_start(data, self.offset);
+        let next_line = terminator
+            .nth_after(data, self.offset, 0)
+            .unwrap_or(data.len());
+        let line_end = line_start + terminator.strip(&data[line_start..next_line]).len();
+        let line_end = line_end.max(self.offset);
+        let mut head = &data[line_start..self.offset];
+        let tail = &data[self.offset..line_end];
+
+        let mut indent_from = &data[line_start..line_end];
+        let mut end = line_start;
+        while indent_from.trim().is_empty() && end > 0 {
+            let start = terminator.nth_before(data, end, 1).unwrap_or(0);
+            let previous = terminator.strip(&data[start..end]);
+            if !previous.trim().is_empty() {
+                indent_from = previous;
+                head = previous;
+            }
+            end = start;
+        }
+
+        let style = config.style.unwrap_or_else(|| {
+            let start = terminator
+                .nth_before(data, line_start, DETECT_LINES)
+                .unwrap_or(0);
+            let end = terminator
+                .nth_after(data, self.offset, DETECT_LINES)
+                .unwrap_or(data.len());
+            IndentStyle::detect_lines(lines_between(data, terminator, start, end))
+        });
+        let mut indent = leading_whitespace(indent_from).to_string();
+        let opens = head
+            .trim_end()
+            .chars()
+            .next_back()
+            .is_some_and(|c| config.openers.contains(c));
+        let closes = tail
+            .trim_start()
+            .chars()
+            .next()
+            .is_some_and(|c| config.closers.contains(c));
+        if opens {
+            indent.push_str(&style.unit());
+        }
+        if closes {
+            match style {
+                _ if indent.ends_with('\t') => {
+                    indent.pop();
+                }
+                IndentStyle::Tabs => {}
+                IndentStyle::Spaces(n) => {
+                    let spaces = indent.len() - indent.trim_end_matches(' ').len();
+                    indent.truncate(indent.len() - spaces.min(n));
+                }
+            }
+        }
+        indent
+    }
+}
+
+/// A structural event from [`Cursor::indent_events`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndentEvent<'a> {
+    /// A line number and the line's text after its indentation.
+    Line(usize, &'a str),
+    /// A new, deeper level of this width was opened.
+    Indent(usize),
+    /// The innermost level was closed.
+    Dedent,
+    Error(IndentError),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndentError {
+    /// The line dedented to a width that matches no open level; `expected`
+    /// is the enclosing level it fell inside. An [`IndentEvent::Indent`] of
+    /// the line's width follows, opening a level for it.
+    InconsistentDedent {
+        line: usize,
+        found: usize,
+        expected: usize,
+    },
+}
+
+/// Iterator over the remaining lines with indentation events between them;
+/// see [`Cursor::indent_events`].
+pub struct IndentEvents<'a, 'b> {
+    cursor: &'b mut Cursor<'a>,
+    levels: Vec<usize>,
+    pending: VecDeque<IndentEvent<'a>>,
+    comment: Option<&'b str>,
+    tab_width: usize,
+}
+
+impl<'b> IndentEvents<'_, 'b> {
+    /// Lines whose text starts with `prefix` after their indentation are
+    /// comments and leave the levels alone, like blank lines.
+    pub fn comment_prefix(mut self, prefix: &'b str) -> Self {
+        self.comment = Some(prefix);
+        self
+    }
+
+    /// A tab advances the width to the next multiple of `width` (8 by
+    /// default, as in Python).
+    pub fn tab_width(mut self, width: usize) -> Self {
+        self.tab_width = width.max(1);
+        self
+    }
+
+    fn width(&self, indent: &str) -> usize {
+        indent.chars().fold(0, |width, c| match c {
+            '\t' => (width / self.tab_width + 1) * self.tab_width,
+            _ => width + 1,
+        })
+    }
+
+    fn change_level(&mut self, line: usize, width: usize) {
+        let top = *self.levels.last().unwrap_or(&0);
+        if width > top {
+            self.levels.push(width);
+            self.pending.push_back(IndentEvent::Indent(width));
+            return;
+        }
+        while self.levels.last().is_some_and(|&level| level > width) {
+            self.levels.pop();
+            self.pending.push_back(IndentEvent::Dedent);
+        }
+        let expected = *self.levels.last().unwrap_or(&0);
+        if expected != width {
+            let error = IndentError::InconsistentDedent {
+                line,
+                found: width,
+                expected,
+            };
+            self.pending.push_back(IndentEvent::Error(error));
+            // Carry on as if the line had opened a level of its own, so that
+            // every Dedent still has its Indent.
+            self.levels.push(width);
+            self.pending.push_back(IndentEvent::Indent(width));
+        }
+    }
+}
+
+impl<'a> Cursor<'a> {
+    /// Iterates over the remaining lines like an indentation-sensitive
+    /// tokenizer: each line is preceded by an [`IndentEvent::Indent`] if it
+    /// is indented deeper than the innermost level, or by one
+    /// [`IndentEvent::Dedent`] per level it closes, and the levels still
+    /// open at the end of the input are closed there. Blank lines do not
+    /// change the levels.
+    pub fn indent_events(&mut self) -> IndentEvents<'a, '_> {
+        IndentEvents {
+            cursor: self,
+            levels: Vec::new(),
+            pending: VecDeque::new(),
+            comment: None,
+            tab_width: 8,
+        }
+    }
+}
+
+impl<'a> Iterator for IndentEvents<'a, '_> {
+    type Item = IndentEvent<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(event) = self.pending.pop_front() {
+            return Some(event);
+        }
+        let line = self.cursor.line();
+        let Some((_, text)) = self.cursor.next_line() else {
+            return self.levels.pop().map(|_| IndentEvent::Dedent);
+        };
+        let content = text.trim_start();
+        let is_comment = self
+            .comment
+            .is_some_and(|prefix| content.starts_with(prefix));
+        if !content.is_empty() && !is_comment {
+            let width = self.width(leading_whitespace(text));
+            self.change_level(line, width);
+        }
+        self.pending.push_back(IndentEvent::Line(line, content));
+        self.pending.pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn indent_at(text: &str, at: &str, config: &IndentConfig) -> String {
+        let mut cursor = Cursor::new(text);
+        cursor.take_until(at).expect("marker");
+        cursor.suggest_indent(config)
+    }
+
+    #[test]
+    fn test_detect_style() {
+        assert_eq!(IndentStyle::detect("a\n  b\n    c"), IndentStyle::Spaces(2));
+        assert_eq!(IndentStyle::detect("a\n\tb"), IndentStyle::Tabs);
+        assert_eq!(IndentStyle::detect("a\nb"), IndentStyle::Spaces(4));
+    }
+
+    #[test]
+    fn test_suggest_indent_openers_and_closers() {
+        let config = IndentConfig::default();
+        let text = "fn main() {\n    if x {\n        y\n    }\n}";
+        assert_eq!(indent_at(text, "\n    if", &config), "    ");
+        assert_eq!(indent_at(text, "\n        y", &config), "        ");
+        assert_eq!(indent_at(text, "\n    }", &config), "        ");
+        assert_eq!(indent_at(text, "}\n}", &config), "");
+
+        let config = IndentConfig {
+            style: Some(IndentStyle::Spaces(2)),
+            ..IndentConfig::default()
+        };
+        assert_eq!(indent_at("def f():\nx", "\n", &config), "  ");
+    }
+
+    #[test]
+    fn test_suggest_indent_mid_line() {
+        let config = IndentConfig::default();
+        let text = "    call(a, b)";
+        assert_eq!(indent_at(text, " b", &config), "    ");
+        assert_eq!(indent_at(text, "a,", &config), "        ");
+    }
+
+    #[test]
+    fn test_suggest_indent_blank_line_inherits() {
+        let config = IndentConfig::default();
+        let mut cursor = Cursor::new("    x = 1\n\n");
+        cursor.take_until("\n\n");
+        cursor.next();
+        assert_eq!(cursor.suggest_indent(&config), "    ");
+
+        let mut cursor = Cursor::new("    x = [\n  \n");
+        cursor.take_until("  \n");
+        assert_eq!(cursor.line(), 1);
+        assert_eq!(cursor.suggest_indent(&config), "        ");
+    }
+
+    #[test]
+    fn test_suggest_indent_follows_line_terminator() {
+        let config = IndentConfig::default();
+        let mut cursor =
+            Cursor::new("fn f() {\r    a\r\r}").with_line_terminator(LineTerminator::Ascii);
+        cursor.take_until("\r\r}");
+        assert_eq!(cursor.suggest_indent(&config), "    ");
+        cursor.next();
+        assert_eq!(cursor.suggest_indent(&config), "    ");
+        cursor.next();
+        assert_eq!(cursor.suggest_indent(&config), "");
+    }
+
+    #[test]
+    fn test_detects_style_near_cursor() {
+        let text = format!("\tfar\n{}if x {{\n  y\n", "z\n".repeat(2 * DETECT_LINES));
+        let mut cursor = Cursor::new(&text);
+        cursor.take_until("\n  y");
+        assert_eq!(cursor.suggest_indent(&IndentConfig::default()), "  ");
+    }
+
+    #[test]
+    fn test_suggest_indent_keeps_tabs() {
+        let config = IndentConfig {
+            style: Some(IndentStyle::Spaces(4)),
+            ..IndentConfig::default()
+        };
+        assert_eq!(indent_at("\t  if x {\n", "\n", &config), "\t      ");
+        assert_eq!(indent_at("\t\tfoo()}", "}", &config), "\t");
+        let tabs = IndentConfig::default();
+        assert_eq!(indent_at("\tif x {\n", "\n", &tabs), "\t\t");
+    }
+
+    fn events(text: &str) -> Vec<String> {
+        Cursor::new(text)
+            .indent_events()
+            .comment_prefix("#")
+            .map(|event| match event {
+                IndentEvent::Line(line, text) => format!("{line}:{text}"),
+                IndentEvent::Indent(width) => format!("INDENT {width}"),
+                IndentEvent::Dedent => "DEDENT".to_string(),
+                IndentEvent::Error(err) => format!("{err:?}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_indent_events() {
+        let text = "if x:\n    y = 1\n\n  # note\n    if z:\n        w()\nelse:\n    pass";
+        assert_eq!(
+            events(text),
+            [
+                "0:if x:", "INDENT 4", "1:y = 1", "2:", "3:# note", "4:if z:", "INDENT 8", "5:w()",
+                "DEDENT", "DEDENT", "6:else:", "INDENT 4", "7:pass", "DEDENT",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_inconsistent_dedent() {
+        let text = "a:\n    b\n  c\n  d\ne\n";
+        assert_eq!(
+            events(text),
+            [
+                "0:a:",
+                "INDENT 4",
+                "1:b",
+                "DEDENT",
+                "InconsistentDedent { line: 2, found: 2, expected: 0 }",
+                "INDENT 2",
+                "2:c",
+                "3:d",
+                "DEDENT",
+                "4:e",
+            ]
+        );
+        let events = events("a:\n        b\n    c\n  d\n      e\n");
+        let indents = events.iter().filter(|e| e.starts_with("INDENT")).count();
+        let dedents = events.iter().filter(|e| *e == "DEDENT").count();
+        assert_eq!((indents, dedents), (4, 4));
+    }
+
+    #[test]
+    fn test_tabs_follow_tab_width() {
+        let text = "a:\n\tb\n        c\n\t  d\n";
+        let widths: Vec<_> = Cursor::new(text)
+            .indent_events()
+            .filter_map(|event| match event {
+                IndentEvent::Indent(width) => Some(width),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(widths, [8, 10]);
+
+        let mut cursor = Cursor::new(text);
+        let mut narrow = cursor.indent_events().tab_width(4);
+        let error = IndentError::InconsistentDedent {
+            line: 3,
+            found: 6,
+            expected: 4,
+        };
+        assert!(narrow.any(|e| e == IndentEvent::Error(error)));
+    }
+}
@@ -0,0 +1,129 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::Cursor;
+
+/// A string interner, so that [`Cursor::words_interned`] can feed an existing
+/// symbol table such as `string-interner` or `lasso` through a thin adapter.
+pub trait Intern {
+    type Symbol: Copy + Eq;
+
+    /// Returns the symbol for `s`, the same one every time `s` is interned.
+    fn intern(&mut self, s: &str) -> Self::Symbol;
+
+    /// Returns the string `symbol` was interned from.
+    fn resolve(&self, symbol: Self::Symbol) -> Option<&str>;
+}
+
+/// A symbol handed out by [`Interner`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Symbol(u32);
+
+/// A minimal [`Intern`] implementation. Each distinct string is stored once.
+#[derive(Debug, Clone, Default)]
+pub struct Interner {
+    symbols: HashMap<Arc<str>, Symbol>,
+    strings: Vec<Arc<str>>,
+}
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of distinct strings interned so far.
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+}
+
+impl Intern for Interner {
+    type Symbol = Symbol;
+
+    fn intern(&mut self, s: &str) -> Symbol {
+        if let Some(&symbol) = self.symbols.get(s) {
+            return symbol;
+        }
+        let symbol = Symbol(u32::try_from(self.strings.len()).expect("too many symbols"));
+        let s: Arc<str> = Arc::from(s);
+        self.strings.push(Arc::clone(&s));
+        self.symbols.insert(s, symbol);
+        symbol
+    }
+
+    fn resolve(&self, symbol: Symbol) -> Option<&str> {
+        self.strings.get(symbol.0 as usize).map(|s| &**s)
+    }
+}
+
+/// Iterator over the remaining words as interned symbols; see
+/// [`Cursor::words_interned`].
+pub struct WordsInterned<'a, 'b, 'i, I> {
+    cursor: &'b mut Cursor<'a>,
+    interner: &'i mut I,
+}
+
+impl<'a> Cursor<'a> {
+    /// Like [`Cursor::words`], but yields each word's symbol in `interner`
+    /// instead of the slice.
+    pub fn words_interned<'i, I: Intern>(
+        &mut self,
+        interner: &'i mut I,
+    ) -> WordsInterned<'a, '_, 'i, I> {
+        WordsInterned {
+            cursor: self,
+            interner,
+        }
+    }
+}
+
+impl<I: Intern> Iterator for WordsInterned<'_, '_, '_, I> {
+    type Item = (usize, I::Symbol);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (offset, word) = self.cursor.next_word()?;
+        Some((offset, self.interner.intern(word)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repeated_words_share_symbols() {
+        let mut interner = Interner::new();
+        let mut cursor = Cursor::new("let x = x + y\nlet y = x");
+        let words: Vec<_> = cursor.words_interned(&mut interner).collect();
+        assert_eq!(words.len(), 10);
+        assert_eq!(interner.len(), 5);
+
+        let symbol = |at: usize| words.iter().find(|&&(off, _)| off == at).unwrap().1;
+        assert_eq!(symbol(0), symbol(14));
+        assert_eq!(symbol(4), symbol(8));
+        assert_eq!(symbol(4), symbol(22));
+        assert_ne!(symbol(4), symbol(12));
+
+        let resolved: Vec<_> = words
+            .iter()
+            .map(|&(_, s)| interner.resolve(s).unwrap())
+            .collect();
+        assert_eq!(
+            resolved,
+            ["let", "x", "=", "x", "+", "y", "let", "y", "=", "x"]
+        );
+    }
+
+    #[test]
+    fn test_interner_survives_across_cursors() {
+        let mut interner = Interner::new();
+        let a = Cursor::new("foo bar").words_interned(&mut interner).last();
+        let b = Cursor::new("bar").words_interned(&mut interner).next();
+        assert_eq!(a.map(|(_, s)| s), b.map(|(_, s)| s));
+        assert_eq!(interner.resolve(Symbol(7)), None);
+    }
+}
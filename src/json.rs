@@ -0,0 +1,126 @@
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::de::DeserializeOwned;
+
+use crate::{Cursor, Span};
+
+/// A line of [`Cursor::json_lines`] input that failed to deserialize.
+#[derive(Debug)]
+pub struct JsonLineError {
+    /// The zero-based line number.
+    pub line: usize,
+    /// The line's bytes, without its terminator.
+    pub span: Span,
+    /// Where in the input serde_json reported the failure.
+    pub offset: usize,
+    pub error: serde_json::Error,
+}
+
+impl fmt::Display for JsonLineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line + 1, self.error)
+    }
+}
+
+impl std::error::Error for JsonLineError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+/// Iterator over newline-delimited JSON values; see [`Cursor::json_lines`].
+pub struct JsonLines<'a, 'b, T> {
+    cursor: &'b mut Cursor<'a>,
+    value: PhantomData<fn() -> T>,
+}
+
+impl<'a> Cursor<'a> {
+    /// Deserializes each remaining non-blank line as a `T`, yielding it with
+    /// its zero-based line number. A malformed line yields an error and the
+    /// iteration carries on with the next line.
+    pub fn json_lines<T: DeserializeOwned>(&mut self) -> JsonLines<'a, '_, T> {
+        JsonLines {
+            cursor: self,
+            value: PhantomData,
+        }
+    }
+}
+
+impl<T: DeserializeOwned> Iterator for JsonLines<'_, '_, T> {
+    type Item = Result<(usize, T), JsonLineError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = self.cursor.line();
+            let (start, text) = self.cursor.next_line()?;
+            if text.trim().is_empty() {
+                continue;
+            }
+            let span = Span::new(start, start + text.len());
+            return Some(match serde_json::from_str(text) {
+                Ok(value) => Ok((line, value)),
+                Err(error) => {
+                    // serde_json columns are one-based byte counts within the
+                    // line, and zero when it has no position to report.
+                    let column = error.column().saturating_sub(1).min(text.len());
+                    Err(JsonLineError {
+                        line,
+                        span,
+                        offset: start + column,
+                        error,
+                    })
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq, serde::Deserialize)]
+    struct Event {
+        level: String,
+        code: u32,
+    }
+
+    #[test]
+    fn test_json_lines() {
+        let log = concat!(
+            "{\"level\": \"info\", \"code\": 1}\n",
+            "\n",
+            "  \r\n",
+            "{\"level\": \"warn\", \"code\": oops}\r\n",
+            "{\"level\": \"error\", \"code\": 3}"
+        );
+        let mut cursor = Cursor::new(log);
+        let results: Vec<_> = cursor.json_lines::<Event>().collect();
+        assert_eq!(results.len(), 3);
+
+        let (line, first) = results[0].as_ref().unwrap();
+        assert_eq!((*line, first.code), (0, 1));
+
+        let err = results[1].as_ref().unwrap_err();
+        assert_eq!(err.line, 3);
+        assert_eq!(err.span, Span::new(34, 65));
+        assert_eq!(&log[err.offset..err.offset + 2], "oo");
+        assert!(err.to_string().starts_with("line 4: "));
+
+        let (line, last) = results[2].as_ref().unwrap();
+        assert_eq!(*line, 4);
+        assert_eq!(last.level, "error");
+    }
+
+    #[test]
+    fn test_truncated_last_line() {
+        let mut cursor = Cursor::new("{\"code\": 1}\n{\"level\": \"info\"");
+        let mut events = cursor.json_lines::<Event>();
+        assert!(events.next().unwrap().is_err());
+        let err = events.next().unwrap().unwrap_err();
+        assert_eq!((err.line, err.span), (1, Span::new(12, 28)));
+        assert!(err.span.start <= err.offset && err.offset <= err.span.end);
+        assert!(events.next().is_none());
+    }
+}
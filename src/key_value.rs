@@ -0,0 +1,143 @@
+use crate::ident::is_ident_continue;
+use crate::{Cursor, Span};
+
+type Slice<'a> = (Span, &'a str);
+
+impl<'a> Cursor<'a> {
+    /// Consumes `key <separator> value` at the cursor, where the key is an
+    /// identifier (see [`Cursor::next_identifier`]) that may also contain
+    /// `-`, as in `Content-Length`, and spaces or tabs may surround the
+    /// separator. A value starting with `"` or `'` is quoted (see
+    /// [`Cursor::next_quoted`]) and its span covers the quotes; any other
+    /// value runs up to the next whitespace and may be empty, so `KEY=` at
+    /// the end of a line has the value `""`.
+    ///
+    /// Works for `.env` lines and attribute lists such as
+    /// `width=3 title="a b"`; for HTTP headers, whose values may contain
+    /// spaces, see [`Cursor::next_key_value_line`]. Returns `None` without moving if there is no
+    /// pair at the cursor or a quoted value is unterminated.
+    pub fn next_key_value(&mut self, separator: &str) -> Option<(Slice<'a>, Slice<'a>)> {
+        self.key_value(separator, false)
+    }
+
+    /// Like [`Cursor::next_key_value`], but the value is the rest of the
+    /// line with trailing whitespace trimmed, quotes and all, as in
+    /// `User-Agent: Mozilla/5.0 (X11)`. The cursor stops at the end of the
+    /// line, before its terminator.
+    pub fn next_key_value_line(&mut self, separator: &str) -> Option<(Slice<'a>, Slice<'a>)> {
+        self.key_value(separator, true)
+    }
+
+    fn key_value(&mut self, separator: &str, to_line_end: bool) -> Option<(Slice<'a>, Slice<'a>)> {
+        let checkpoint = self.checkpoint();
+        let pair = self.scan_key_value(separator, to_line_end);
+        if pair.is_none() {
+            self.restore(checkpoint);
+        }
+        pair
+    }
+
+    fn scan_key_value(
+        &mut self,
+        separator: &str,
+        to_line_end: bool,
+    ) -> Option<(Slice<'a>, Slice<'a>)> {
+        let is_blank = |c: char| c == ' ' || c == '\t';
+        let (start, _) = self.next_identifier()?;
+        self.consume_while(|c| c == '-' || is_ident_continue(c));
+        let key = (
+            Span::new(start, self.offset),
+            &self.data[start..self.offset],
+        );
+        self.consume_while(is_blank);
+        if !self.eat_str(separator) {
+            return None;
+        }
+        self.consume_while(is_blank);
+        if to_line_end {
+            let start = self.offset;
+            let next_line = self
+                .terminator
+                .nth_after(self.data, start, 0)
+                .unwrap_or(self.data.len());
+            let line = self.terminator.strip(&self.data[start..next_line]);
+            self.advance_to(start + line.len());
+            let value = line.trim_end();
+            return Some((key, (Span::new(start, start + value.len()), value)));
+        }
+        let value = match self.peek_char() {
+            Some(quote @ ('"' | '\'')) => self.next_quoted(quote).ok()?,
+            _ => {
+                let start = self.offset;
+                let (_, bare) = self.consume_while(|c| !c.is_whitespace());
+                (Span::new(start, self.offset), bare)
+            }
+        };
+        Some((key, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LineTerminator;
+
+    fn texts<'a>(pair: (Slice<'a>, Slice<'a>)) -> (&'a str, &'a str) {
+        (pair.0 .1, pair.1 .1)
+    }
+
+    #[test]
+    fn test_key_values() {
+        let mut cursor = Cursor::new("width=3 title=\"a b\" empty=\nx");
+        let (key, value) = cursor.next_key_value("=").unwrap();
+        assert_eq!(
+            (key, value),
+            ((Span::new(0, 5), "width"), (Span::new(6, 7), "3"))
+        );
+        cursor.skip_whitespace();
+        let (_, value) = cursor.next_key_value("=").unwrap();
+        assert_eq!(value, (Span::new(14, 19), "a b"));
+        cursor.skip_whitespace();
+        assert_eq!(cursor.next_key_value("=").map(texts), Some(("empty", "")));
+        cursor.skip_whitespace();
+        assert_eq!(cursor.next_key_value("="), None);
+        assert_eq!(cursor.peek_char(), Some('x'));
+
+        let mut cursor = Cursor::new("Content-Length : 42\r\n");
+        assert_eq!(
+            cursor.next_key_value(":").map(texts),
+            Some(("Content-Length", "42"))
+        );
+        let mut cursor = Cursor::new("Host:\texample.com\r\n");
+        assert_eq!(
+            cursor.next_key_value(":").map(texts),
+            Some(("Host", "example.com"))
+        );
+    }
+
+    #[test]
+    fn test_header_values_run_to_line_end() {
+        let text = "User-Agent: Mozilla/5.0 (X11) \r\nContent-Type:text/html; charset=utf-8\rX:";
+        let mut cursor = Cursor::new(text).with_line_terminator(LineTerminator::Ascii);
+        let (_, value) = cursor.next_key_value_line(":").unwrap();
+        assert_eq!(value, (Span::new(12, 29), "Mozilla/5.0 (X11)"));
+        assert_eq!(cursor.offset(), 30);
+        cursor.next_line();
+        assert_eq!(
+            cursor.next_key_value_line(":").map(texts),
+            Some(("Content-Type", "text/html; charset=utf-8"))
+        );
+        cursor.next_line();
+        assert_eq!(cursor.next_key_value_line(":").map(texts), Some(("X", "")));
+        assert!(cursor.is_at_end());
+    }
+
+    #[test]
+    fn test_failed_pair_does_not_move() {
+        for text in ["key 'open", "key value", "=value", "-x=1"] {
+            let mut cursor = Cursor::new(text);
+            assert_eq!(cursor.next_key_value("="), None);
+            assert!(cursor.is_at_start(), "{text:?}");
+        }
+    }
+}
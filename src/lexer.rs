@@ -0,0 +1,234 @@
+use std::fmt;
+
+use crate::{Cursor, Position, Span, Token};
+
+type Matcher<'a> = Box<dyn Fn(&mut Cursor<'a>) -> bool + 'a>;
+type MakeToken<'a, T> = Box<dyn Fn(&'a str) -> T + 'a>;
+
+struct Rule<'a, T> {
+    matcher: Matcher<'a>,
+    priority: i32,
+    token: MakeToken<'a, T>,
+}
+
+/// A lexer built from rules; see [`Lexer::builder`].
+///
+/// At each position every rule is tried and the longest match wins. Among
+/// matches of the same length the rule with the highest priority wins, then
+/// the one registered first, so a keyword rule with a higher priority beats
+/// an identifier rule on `if` but not on `iffy`.
+pub struct Lexer<'a, T> {
+    rules: Vec<Rule<'a, T>>,
+    skip: Option<fn(char) -> bool>,
+}
+
+/// Builds a [`Lexer`] one rule at a time.
+pub struct LexerBuilder<'a, T> {
+    lexer: Lexer<'a, T>,
+}
+
+/// No rule matched at `position`, where the input has `found`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LexError {
+    pub found: char,
+    pub position: Position,
+}
+
+impl fmt::Display for LexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unexpected {:?} at {}", self.found, self.position)
+    }
+}
+
+impl std::error::Error for LexError {}
+
+impl<'a, T> Lexer<'a, T> {
+    pub fn builder() -> LexerBuilder<'a, T> {
+        LexerBuilder {
+            lexer: Lexer {
+                rules: Vec::new(),
+                skip: None,
+            },
+        }
+    }
+
+    /// Lexes one token at the cursor, after skipping any chars the lexer
+    /// skips. Returns `None` at the end of the input; on an error the cursor
+    /// stays on the offending char.
+    pub fn next_token(&self, cursor: &mut Cursor<'a>) -> Option<Result<Token<'a, T>, LexError>> {
+        if let Some(skip) = self.skip {
+            cursor.consume_while(skip);
+        }
+        let start = cursor.checkpoint();
+        let found = cursor.peek_char()?;
+        let mut best: Option<(usize, &Rule<'a, T>)> = None;
+        for rule in &self.rules {
+            let matched = (rule.matcher)(cursor);
+            let end = cursor.offset();
+            cursor.restore(start);
+            if !matched || end <= start.offset() {
+                continue;
+            }
+            let better = best
+                .is_none_or(|(best_end, best)| (end, rule.priority) > (best_end, best.priority));
+            if better {
+                best = Some((end, rule));
+            }
+        }
+        let Some((end, rule)) = best else {
+            let position = Cursor::position(cursor);
+            return Some(Err(LexError { found, position }));
+        };
+        cursor.advance_to(end);
+        let text = &cursor.data[start.offset()..end];
+        Some(Ok(Token {
+            kind: (rule.token)(text),
+            span: Span::new(start.offset(), end),
+            text,
+        }))
+    }
+
+    /// Iterates over the tokens from the cursor on. After an error the
+    /// offending char is skipped, so lexing carries on with the next one.
+    pub fn tokens<'c>(&'c self, cursor: &'c mut Cursor<'a>) -> LexerTokens<'a, 'c, T> {
+        LexerTokens {
+            lexer: self,
+            cursor,
+        }
+    }
+}
+
+impl<'a, T> LexerBuilder<'a, T> {
+    /// Matches `literal` exactly.
+    pub fn literal(
+        self,
+        literal: &'a str,
+        priority: i32,
+        token: impl Fn(&'a str) -> T + 'a,
+    ) -> Self {
+        self.rule(priority, move |cursor| cursor.eat_str(literal), token)
+    }
+
+    /// Matches a non-empty run of chars in `class`.
+    pub fn class(
+        self,
+        class: fn(char) -> bool,
+        priority: i32,
+        token: impl Fn(&'a str) -> T + 'a,
+    ) -> Self {
+        self.rule(
+            priority,
+            move |cursor| !cursor.consume_while(class).1.is_empty(),
+            token,
+        )
+    }
+
+    /// Matches with `matcher`, which moves the cursor over the match and
+    /// returns whether it found one. The cursor is restored after every
+    /// attempt, so a matcher is free to backtrack or give up midway. Empty
+    /// matches are ignored.
+    pub fn rule(
+        mut self,
+        priority: i32,
+        matcher: impl Fn(&mut Cursor<'a>) -> bool + 'a,
+        token: impl Fn(&'a str) -> T + 'a,
+    ) -> Self {
+        self.lexer.rules.push(Rule {
+            matcher: Box::new(matcher),
+            priority,
+            token: Box::new(token),
+        });
+        self
+    }
+
+    /// Skips runs of chars in `class`, such as whitespace, before each
+    /// token.
+    pub fn skip(mut self, class: fn(char) -> bool) -> Self {
+        self.lexer.skip = Some(class);
+        self
+    }
+
+    pub fn build(self) -> Lexer<'a, T> {
+        self.lexer
+    }
+}
+
+/// Iterator over the tokens of a [`Lexer`]; see [`Lexer::tokens`].
+pub struct LexerTokens<'a, 'c, T> {
+    lexer: &'c Lexer<'a, T>,
+    cursor: &'c mut Cursor<'a>,
+}
+
+impl<'a, T> Iterator for LexerTokens<'a, '_, T> {
+    type Item = Result<Token<'a, T>, LexError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let token = self.lexer.next_token(self.cursor)?;
+        if token.is_err() {
+            self.cursor.next();
+        }
+        Some(token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Tok<'a> {
+        If,
+        Ident(&'a str),
+        Int(i64),
+        Op,
+        Str,
+    }
+
+    fn is_ident(c: char) -> bool {
+        c.is_alphanumeric() || c == '_'
+    }
+
+    fn lexer<'a>() -> Lexer<'a, Tok<'a>> {
+        Lexer::builder()
+            .skip(char::is_whitespace)
+            .class(is_ident, 0, Tok::Ident)
+            .literal("if", 1, |_| Tok::If)
+            .class(|c| c.is_ascii_digit(), 1, |s| Tok::Int(s.parse().unwrap()))
+            .literal("=", 0, |_| Tok::Op)
+            .literal("==", 0, |_| Tok::Op)
+            .rule(0, |c| c.next_quoted('"').is_ok(), |_| Tok::Str)
+            .build()
+    }
+
+    #[test]
+    fn test_longest_match_then_priority() {
+        let lexer = lexer();
+        let mut cursor = Cursor::new("if iffy == 42 \"a b\"");
+        let tokens: Vec<_> = lexer.tokens(&mut cursor).map(Result::unwrap).collect();
+        let kinds: Vec<_> = tokens.iter().map(|t| t.kind.clone()).collect();
+        assert_eq!(
+            kinds,
+            [Tok::If, Tok::Ident("iffy"), Tok::Op, Tok::Int(42), Tok::Str]
+        );
+        assert_eq!(tokens[2].span, Span::new(8, 10));
+        assert_eq!(tokens[4].text, "\"a b\"");
+        assert!(cursor.is_at_end());
+    }
+
+    #[test]
+    fn test_errors_skip_one_char() {
+        let lexer = lexer();
+        let mut cursor = Cursor::new("x\n  ? \"open");
+        let mut tokens = lexer.tokens(&mut cursor);
+        assert_eq!(tokens.next().unwrap().unwrap().kind, Tok::Ident("x"));
+        let err = tokens.next().unwrap().unwrap_err();
+        assert_eq!(
+            (err.found, err.position.line, err.position.column),
+            ('?', 1, 2)
+        );
+        assert_eq!(err.to_string(), "unexpected '?' at 2:3");
+        assert_eq!(tokens.next().unwrap().unwrap_err().found, '"');
+        assert_eq!(tokens.next().unwrap().unwrap().kind, Tok::Ident("open"));
+        assert!(tokens.next().is_none());
+    }
+}
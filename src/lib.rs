@@ -1,8 +1,105 @@
-use std::str::CharIndices;
+use std::borrow::Cow;
 
-fn is_newline(c: char) -> bool {
-    c == '\n'
-}
+mod balanced;
+mod case;
+mod checkpoint;
+#[cfg(feature = "chumsky")]
+mod chumsky_input;
+mod column_unit;
+mod columns;
+mod combinator;
+mod compare;
+mod cursor_mut;
+mod dispatch;
+mod edit;
+mod error;
+mod fields;
+mod gap_buffer;
+#[cfg(feature = "segmentation")]
+mod grapheme;
+mod ident;
+mod indent;
+mod intern;
+#[cfg(feature = "serde_json")]
+mod json;
+mod key_value;
+mod lexer;
+mod line_index;
+#[cfg(feature = "logos")]
+mod logos_adapter;
+#[cfg(feature = "lsp")]
+mod lsp;
+#[cfg(feature = "nom")]
+mod nom_input;
+#[cfg(feature = "unicode-normalization")]
+mod normalize;
+mod number;
+mod paragraph;
+mod parse;
+mod piece_table;
+mod position;
+mod quoted;
+#[cfg(feature = "regex")]
+mod regex_match;
+mod resume;
+mod search;
+mod seek;
+mod sentence;
+mod span;
+mod suggest;
+mod syntax;
+mod terminator;
+#[cfg(test)]
+mod test_util;
+mod token;
+mod trivia;
+#[cfg(feature = "winnow")]
+mod winnow_stream;
+mod words;
+
+pub use balanced::BalanceError;
+pub use checkpoint::{Checkpoint, Transaction};
+#[cfg(feature = "chumsky")]
+pub use chumsky_input::LineColInput;
+pub use column_unit::ColumnUnit;
+pub use columns::Columns;
+pub use combinator::{BetweenError, ManyError, Recoverable, Separated, SeparatedError, Trailing};
+pub use cursor_mut::{CursorMut, TextBuffer};
+pub use dispatch::{Dispatch, DispatchBuilder, DispatchError, Handler};
+pub use edit::{Edit, EditBuilder, EditError, OverlapPolicy};
+pub use error::{ContextFrame, CursorError, ParseError};
+pub use fields::CursorFields;
+pub use gap_buffer::GapBuffer;
+#[cfg(feature = "segmentation")]
+pub use grapheme::CursorGraphemes;
+pub use indent::{IndentConfig, IndentError, IndentEvent, IndentEvents, IndentStyle};
+pub use intern::{Intern, Interner, Symbol, WordsInterned};
+#[cfg(feature = "serde_json")]
+pub use json::{JsonLineError, JsonLines};
+pub use lexer::{LexError, Lexer, LexerBuilder, LexerTokens};
+pub use line_index::LineIndex;
+#[cfg(feature = "logos")]
+pub use logos_adapter::LogosTokens;
+pub use number::{NumberLit, Numbers};
+pub use paragraph::CursorParagraphs;
+pub use parse::ParseNextError;
+pub use piece_table::PieceTable;
+pub use position::{LineCols, Position};
+pub use quoted::{QuoteError, WordsQuoted};
+#[cfg(feature = "regex")]
+pub use regex_automata::meta::Regex;
+pub use resume::{PositionToken, ResumeError};
+pub use search::{FindAll, Matches, Needle, SplitStr};
+pub use seek::SeekError;
+pub use sentence::CursorSentences;
+pub use span::{Span, SpanSet};
+pub use syntax::{ContextTracker, StringDelimiter, SyntaxConfig, SyntaxContext};
+pub use terminator::LineTerminator;
+pub use token::{CursorTokens, Token, TokenBuffer, TokenKind, WordsLossless};
+pub use trivia::TriviaConfig;
+#[cfg(feature = "segmentation")]
+pub use words::UnicodeWords;
+pub use words::WordSplitter;
 
 /// A cursor that can move both forward and backward through a string.
 #[derive(Debug, Clone)]
@@ -10,7 +107,17 @@ pub struct Cursor<'a> {
     data: &'a str,
     offset: usize,
     line: usize,
-    forward: CharIndices<'a>,
+    column: usize,
+    /// Chars before the cursor.
+    char_idx: usize,
+    marks: Vec<(Cow<'static, str>, Checkpoint)>,
+    terminator: LineTerminator,
+    column_unit: ColumnUnit,
+    splitter: Option<words::SharedSplitter>,
+    trivia: Option<std::sync::Arc<TriviaConfig>>,
+    line_index: Option<std::sync::Arc<LineIndex>>,
+    #[cfg(feature = "unicode-normalization")]
+    nfc: bool,
 }
 
 impl<'a> Cursor<'a> {
@@ -19,24 +126,114 @@ impl<'a> Cursor<'a> {
             data,
             offset: 0,
             line: 0,
-            forward: data.char_indices(),
+            column: 0,
+            char_idx: 0,
+            marks: Vec::new(),
+            terminator: LineTerminator::Lf,
+            column_unit: ColumnUnit::Char,
+            splitter: None,
+            trivia: None,
+            line_index: None,
+            #[cfg(feature = "unicode-normalization")]
+            nfc: false,
         }
     }
 
-    fn backward(&self) -> CharIndices<'a> {
-        self.data[..self.offset].char_indices()
+    /// Creates a cursor positioned at the byte offset `offset`.
+    pub fn new_at(data: &'a str, offset: usize) -> Result<Self, SeekError> {
+        let mut cursor = Self::new(data);
+        cursor.seek_to_offset(offset)?;
+        Ok(cursor)
+    }
+
+    /// Creates a cursor positioned at the end of `data`, ready to move
+    /// backward.
+    pub fn new_at_end(data: &'a str) -> Self {
+        let mut cursor = Self::new(data);
+        cursor.advance_to(data.len());
+        cursor
+    }
+
+    fn forward(&self) -> &'a str {
+        &self.data[self.offset..]
+    }
+
+    fn backward(&self) -> &'a str {
+        &self.data[..self.offset]
+    }
+
+    /// Moves the cursor forward to the byte offset `end`, counting the line
+    /// terminators in the skipped region in bulk.
+    fn advance_to(&mut self, end: usize) {
+        debug_assert!(end >= self.offset && self.data.is_char_boundary(end));
+        let lines = self.line_of(end) - self.line;
+        let since = if lines > 0 {
+            self.line += lines;
+            self.column = 0;
+            self.line_start_at(end)
+        } else {
+            self.offset
+        };
+        self.column += self.column_unit.width(&self.data[since..end]);
+        self.char_idx += self.data[self.offset..end].chars().count();
+        self.offset = end;
+    }
+
+    /// Moves the cursor back to the byte offset `start`, the mirror of
+    /// [`Cursor::advance_to`].
+    fn retreat_to(&mut self, start: usize) {
+        debug_assert!(start <= self.offset && self.data.is_char_boundary(start));
+        let lines = self.line - self.line_of(start);
+        let chars = self.data[start..self.offset].chars().count();
+        self.line -= lines;
+        self.column = if lines == 0 {
+            self.column - self.column_unit.width(&self.data[start..self.offset])
+        } else {
+            self.column_at(start)
+        };
+        self.char_idx -= chars;
+        self.offset = start;
+    }
+
+    /// Moves forward `n` chars, or to the end if fewer remain, returning how
+    /// many it moved.
+    pub fn advance_by(&mut self, n: usize) -> usize {
+        let (mut moved, mut end) = (0, self.offset);
+        for (i, c) in self.forward().char_indices().take(n) {
+            moved += 1;
+            end = self.offset + i + c.len_utf8();
+        }
+        self.advance_to(end);
+        moved
+    }
+
+    /// Moves back `n` chars, or to the start if fewer precede the cursor,
+    /// returning how many it moved.
+    pub fn retreat_by(&mut self, n: usize) -> usize {
+        let (mut moved, mut start) = (0, self.offset);
+        for (i, _) in self.backward().char_indices().rev().take(n) {
+            moved += 1;
+            start = i;
+        }
+        self.retreat_to(start);
+        moved
     }
 
     pub fn next_char(&mut self) -> Option<char> {
         self.next().map(|(_, c)| c)
     }
 
+    /// Skips leading whitespace and returns the run of non-whitespace chars
+    /// that follows, leaving the cursor just after it. A
+    /// [`WordSplitter`] set on the cursor decides what a word is instead.
     pub fn next_word(&mut self) -> Option<(usize, &'a str)> {
+        if let Some(splitter) = self.splitter.clone() {
+            return self.split_next_word(&*splitter.0);
+        }
+        self.skip_whitespace();
         let start = self.offset;
-        for (_, c) in self.by_ref() {
-            if c.is_whitespace() {
-                break;
-            }
+        while self.peek_char().is_some_and(|c| !c.is_whitespace()) {
+            self.next();
         }
         let end = self.offset;
         if start < end {
@@ -46,13 +243,50 @@ impl<'a> Cursor<'a> {
         }
     }
 
+    /// Skips whitespace before the cursor and moves back over the word that
+    /// precedes it, returning the word's start offset and text.
+    pub fn prev_word(&mut self) -> Option<(usize, &'a str)> {
+        if let Some(splitter) = self.splitter.clone() {
+            return self.split_prev_word(&*splitter.0);
+        }
+        self.skip_whitespace_back();
+        let end = self.offset;
+        while self.lookback_char().is_some_and(|c| !c.is_whitespace()) {
+            self.prev();
+        }
+        let start = self.offset;
+        (start < end).then(|| (start, &self.data[start..end]))
+    }
+
+    /// The word [`Cursor::next_word`] would return, without moving.
+    pub fn peek_word(&self) -> Option<(usize, &'a str)> {
+        self.peek_nth_word(0)
+    }
+
+    /// Returns the `n`th upcoming word (`0` being the one [`Cursor::next_word`]
+    /// would return) without moving the cursor. Each call walks the `n`
+    /// words before it; to look at several in a row use [`Cursor::peek_words`].
+    pub fn peek_nth_word(&self, n: usize) -> Option<(usize, &'a str)> {
+        self.peek_words().nth(n)
+    }
+
+    /// The upcoming words, read from a copy of the cursor so it doesn't move.
+    pub fn peek_words(&self) -> impl Iterator<Item = (usize, &'a str)> {
+        let mut ahead = self.clone();
+        std::iter::from_fn(move || ahead.next_word())
+    }
+
+    /// Consumes the rest of the line and its terminator, returning the line's
+    /// start offset and its text without the terminator.
     pub fn next_line(&mut self) -> Option<(usize, &'a str)> {
+        let (start, line) = self.next_line_with_ending()?;
+        Some((start, self.terminator.strip(line)))
+    }
+
+    /// Like [`Cursor::next_line`], but keeps the terminator in the text.
+    pub fn next_line_with_ending(&mut self) -> Option<(usize, &'a str)> {
         let start = self.offset;
-        for (_, c) in self.by_ref() {
-            if is_newline(c) {
-                break;
-            }
-        }
+        self.advance_to(self.lines_end(1));
         let end = self.offset;
         if start < end {
             Some((start, &self.data[start..end]))
@@ -61,42 +295,124 @@ impl<'a> Cursor<'a> {
         }
     }
 
-    pub fn peek_line(&mut self) -> Option<(usize, &'a str)> {
-        let start = self.offset;
-        for (_, c) in self.forward.clone() {
-            if is_newline(c) {
-                break;
-            }
-        }
+    /// Moves back to the start of the previous line, returning it without its
+    /// terminator. From the middle of a line, moves back to that line's start
+    /// instead, so this undoes [`Cursor::next_line`].
+    pub fn prev_line(&mut self) -> Option<(usize, &'a str)> {
         let end = self.offset;
-        if start < end {
-            Some((start, &self.data[start..end]))
+        let search = match self.lookback() {
+            Some((at, c)) if self.terminator.ends_line(self.data, at, c) => at,
+            _ => end,
+        };
+        let start = self.terminator.line_start(self.data, search);
+        if start == end {
+            return None;
+        }
+        self.retreat_to(start);
+        Some((start, self.terminator.strip(&self.data[start..end])))
+    }
+
+    /// The line [`Cursor::next_line`] would return, without moving.
+    pub fn peek_line(&self) -> Option<(usize, &'a str)> {
+        let end = self.lines_end(1);
+        if self.offset < end {
+            let line = &self.data[self.offset..end];
+            Some((self.offset, self.terminator.strip(line)))
         } else {
             None
         }
     }
 
-    fn skip_whitespace(&mut self) {
-        while let Some((_, c)) = self.peek() {
-            if !c.is_whitespace() {
-                break;
-            }
-            self.next();
+    /// Byte offset just past the terminator of the `n`th remaining line, or
+    /// the end of the input if fewer lines remain.
+    fn lines_end(&self, n: usize) -> usize {
+        match n.checked_sub(1) {
+            None => self.offset,
+            Some(last) => self
+                .terminator
+                .nth_after(self.data, self.offset, last)
+                .unwrap_or(self.data.len()),
         }
     }
 
-    pub fn prev(&mut self) -> Option<(usize, char)> {
-        let mut backward = self.backward();
+    /// A cursor over just the next `n` lines, positioned here and ending
+    /// after the `n`th line's terminator. Offsets and line numbers stay those
+    /// of the full input. This cursor does not move.
+    pub fn peek_lines(&self, n: usize) -> Cursor<'a> {
+        Cursor {
+            data: &self.data[..self.lines_end(n)],
+            ..self.clone()
+        }
+    }
 
-        let last_byte_len = backward.as_str().as_bytes().len();
-        let (pos, c) = backward.next_back()?;
-        let cur_byte_len = backward.as_str().as_bytes().len();
-        self.offset -= last_byte_len - cur_byte_len;
+    /// Like [`Cursor::peek_lines`], but also moves this cursor to the start
+    /// of the line after them.
+    pub fn take_lines(&mut self, n: usize) -> Cursor<'a> {
+        let head = self.peek_lines(n);
+        self.advance_to(head.data.len());
+        head
+    }
 
-        self.forward = self.data[self.offset..].char_indices();
+    /// Skips whitespace, as defined by [`char::is_whitespace`], and reports
+    /// what was skipped.
+    pub fn skip_whitespace(&mut self) -> SkippedWs {
+        let start = self.offset;
+        let line = self.line;
+        let len = self.forward().len() - self.forward().trim_start().len();
+        self.advance_to(start + len);
+        SkippedWs {
+            span: Span::new(start, self.offset),
+            lines: self.line - line,
+        }
+    }
 
-        if is_newline(c) {
+    /// Skips whitespace before the cursor, moving backwards.
+    pub fn skip_whitespace_back(&mut self) -> SkippedWs {
+        let end = self.offset;
+        let start = self.backward().trim_end().len();
+        let line = self.line;
+        self.retreat_to(start);
+        SkippedWs {
+            span: Span::new(start, end),
+            lines: line - self.line,
+        }
+    }
+
+    /// Zero-based column of the byte offset `offset`.
+    fn column_at(&self, offset: usize) -> usize {
+        let line_start = self.line_start_at(offset);
+        self.column_unit.width(&self.data[line_start..offset])
+    }
+
+    /// Consumes `c` if it is the next char, returning whether it did.
+    pub fn eat_char(&mut self, c: char) -> bool {
+        let matched = self.peek_char() == Some(c);
+        if matched {
+            self.next();
+        }
+        matched
+    }
+
+    /// Consumes `s` if the remaining input starts with it, returning whether
+    /// it did.
+    pub fn eat_str(&mut self, mut s: &str) -> bool {
+        let Some(len) = self.match_len(&mut s) else {
+            return false;
+        };
+        self.advance_to(self.offset + len);
+        true
+    }
+
+    pub fn prev(&mut self) -> Option<(usize, char)> {
+        let (pos, c) = self.lookback()?;
+        self.offset = pos;
+        self.char_idx -= 1;
+
+        if self.terminator.ends_line(self.data, pos, c) {
             self.line -= 1;
+            self.column = self.column_at(pos);
+        } else {
+            self.column -= self.column_unit.width_of(c);
         }
 
         Some((pos, c))
@@ -107,25 +423,98 @@ impl<'a> Cursor<'a> {
     }
 
     pub fn peek(&self) -> Option<(usize, char)> {
-        self.forward.clone().next()
+        self.forward().chars().next().map(|c| (self.offset, c))
     }
 
     pub fn peek_char(&self) -> Option<char> {
         self.peek().map(|(_, c)| c)
     }
 
+    /// The `n`th char ahead, where `peek_n(0)` equals [`Cursor::peek`].
+    pub fn peek_n(&self, n: usize) -> Option<(usize, char)> {
+        let (i, c) = self.forward().char_indices().nth(n)?;
+        Some((self.offset + i, c))
+    }
+
+    /// The `n`th char behind, where `lookback_n(0)` equals
+    /// [`Cursor::lookback`].
+    pub fn lookback_n(&self, n: usize) -> Option<(usize, char)> {
+        self.backward().char_indices().nth_back(n)
+    }
+
+    /// The next `n` chars as a slice, or `None` if fewer remain.
+    pub fn peek_str(&self, n: usize) -> Option<&'a str> {
+        let rest = self.forward();
+        if n == 0 {
+            return Some("");
+        }
+        let (i, c) = rest.char_indices().nth(n - 1)?;
+        Some(&rest[..i + c.len_utf8()])
+    }
+
+    /// The previous `n` chars as a slice, or `None` if fewer precede the
+    /// cursor.
+    pub fn lookback_str(&self, n: usize) -> Option<&'a str> {
+        let before = self.backward();
+        if n == 0 {
+            return Some("");
+        }
+        let (i, _) = before.char_indices().nth_back(n - 1)?;
+        Some(&before[i..])
+    }
+
     pub fn lookback(&self) -> Option<(usize, char)> {
-        self.backward().next_back()
+        self.backward().char_indices().next_back()
     }
 
     pub fn lookback_char(&self) -> Option<char> {
         self.lookback().map(|(_, c)| c)
     }
 
+    /// The text ahead of the cursor.
+    pub fn remaining(&self) -> &'a str {
+        self.forward()
+    }
+
+    /// The text behind the cursor.
+    pub fn consumed(&self) -> &'a str {
+        self.backward()
+    }
+
+    pub const fn is_at_start(&self) -> bool {
+        self.offset == 0
+    }
+
+    pub const fn is_at_end(&self) -> bool {
+        self.offset == self.data.len()
+    }
+
+    /// Bytes left ahead of the cursor.
+    pub const fn remaining_len(&self) -> usize {
+        self.data.len() - self.offset
+    }
+
+    /// Chars left ahead of the cursor. Unlike [`Cursor::remaining_len`], this
+    /// has to count them.
+    pub fn remaining_chars(&self) -> usize {
+        self.forward().chars().count()
+    }
+
+    /// Byte offset of the cursor.
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
     pub const fn line(&self) -> usize {
         self.line
     }
 
+    /// Zero-based column of the cursor, counted in chars unless set
+    /// otherwise with [`Cursor::with_column_unit`].
+    pub const fn column(&self) -> usize {
+        self.column
+    }
+
     pub const fn words(&mut self) -> CursorWords<'a, '_> {
         CursorWords::new(self)
     }
@@ -137,25 +526,73 @@ impl<'a> Cursor<'a> {
     pub const fn lines(&mut self) -> CursorLines<'a, '_> {
         CursorLines::new(self)
     }
+
+    /// Like [`Cursor::lines`], but each line keeps its terminator, so the
+    /// yielded texts concatenate back to the remaining input.
+    pub const fn lines_with_endings(&mut self) -> CursorLines<'a, '_, true> {
+        CursorLines::with_endings(self)
+    }
+
+    /// Like [`Cursor::words`], but yields each word's full byte span.
+    pub fn words_spanned(&mut self) -> impl Iterator<Item = (Span, &'a str)> + '_ {
+        self.words()
+            .map(|(start, word)| (Span::new(start, start + word.len()), word))
+    }
+
+    /// Like [`Cursor::lines`], but yields each line's full byte span instead
+    /// of its start offset, alongside its line number.
+    pub fn lines_spanned(&mut self) -> impl Iterator<Item = (Span, usize, &'a str)> + '_ {
+        self.lines()
+            .map(|(start, line_no, line)| (Span::new(start, start + line.len()), line_no, line))
+    }
+
+    /// Iterates backward over the words before the cursor, moving it to the
+    /// start of each.
+    pub const fn words_rev(&mut self) -> CursorWordsRev<'a, '_> {
+        CursorWordsRev { cursor: self }
+    }
+
+    /// Iterates backward over the lines before the cursor, yielding each
+    /// one's line number and text as [`Cursor::prev_line`] returns it.
+    pub const fn lines_rev(&mut self) -> CursorLinesRev<'a, '_> {
+        CursorLinesRev { cursor: self }
+    }
 }
 
 impl Iterator for Cursor<'_> {
     type Item = (usize, char);
 
     fn next(&mut self) -> Option<Self::Item> {
-        let last_byte_len = self.forward.as_str().as_bytes().len();
-        let (pos, c) = self.forward.next()?;
-        let cur_byte_len = self.forward.as_str().as_bytes().len();
-        self.offset += last_byte_len - cur_byte_len;
+        let (pos, c) = self.peek()?;
+        self.offset += c.len_utf8();
+        self.char_idx += 1;
 
-        if is_newline(c) {
+        if self.terminator.ends_line(self.data, pos, c) {
             self.line += 1;
+            self.column = 0;
+        } else {
+            self.column += self.column_unit.width_of(c);
         }
 
         Some((pos, c))
     }
 }
 
+/// The whitespace passed over by [`Cursor::skip_whitespace`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SkippedWs {
+    pub span: Span,
+    /// Line terminators crossed.
+    pub lines: usize,
+}
+
+impl SkippedWs {
+    /// Whether nothing was skipped.
+    pub const fn is_empty(&self) -> bool {
+        self.span.is_empty()
+    }
+}
+
 pub struct CursorWords<'a, 'b, const LINES: bool = false> {
     cursor: &'b mut Cursor<'a>,
 }
@@ -172,6 +609,23 @@ impl<'a, 'b> CursorWords<'a, 'b, true> {
     }
 }
 
+impl<'a, 'b, const LINES: bool> CursorWords<'a, 'b, LINES> {
+    /// The input that has not been yielded yet, starting where the next word
+    /// does, or `None` once no word is left.
+    pub fn remainder(&self) -> Option<&'a str> {
+        let rest = self.cursor.forward();
+        let start = match &self.cursor.splitter {
+            Some(splitter) => splitter.0.next_word(rest)?.start,
+            None => rest.len() - rest.trim_start().len(),
+        };
+        Some(&rest[start..]).filter(|rest| !rest.is_empty())
+    }
+
+    pub fn into_cursor(self) -> &'b mut Cursor<'a> {
+        self.cursor
+    }
+}
+
 impl<'a> Iterator for CursorWords<'a, '_, false> {
     type Item = (usize, &'a str);
 
@@ -193,7 +647,32 @@ impl<'a> Iterator for CursorWords<'a, '_, true> {
     }
 }
 
-pub struct CursorLines<'a, 'b> {
+pub struct CursorWordsRev<'a, 'b> {
+    cursor: &'b mut Cursor<'a>,
+}
+
+impl<'a> Iterator for CursorWordsRev<'a, '_> {
+    type Item = (usize, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cursor.prev_word()
+    }
+}
+
+pub struct CursorLinesRev<'a, 'b> {
+    cursor: &'b mut Cursor<'a>,
+}
+
+impl<'a> Iterator for CursorLinesRev<'a, '_> {
+    type Item = (usize, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (_, line) = self.cursor.prev_line()?;
+        Some((self.cursor.line(), line))
+    }
+}
+
+pub struct CursorLines<'a, 'b, const ENDINGS: bool = false> {
     cursor: &'b mut Cursor<'a>,
 }
 
@@ -203,12 +682,34 @@ impl<'a, 'b> CursorLines<'a, 'b> {
     }
 }
 
-impl<'a> Iterator for CursorLines<'a, '_> {
+impl<'a, 'b> CursorLines<'a, 'b, true> {
+    pub const fn with_endings(cursor: &'b mut Cursor<'a>) -> Self {
+        Self { cursor }
+    }
+}
+
+impl<'a, 'b, const ENDINGS: bool> CursorLines<'a, 'b, ENDINGS> {
+    /// The input that has not been yielded yet, starting where the next line
+    /// does, or `None` once the input is exhausted.
+    pub fn remainder(&self) -> Option<&'a str> {
+        Some(self.cursor.forward()).filter(|rest| !rest.is_empty())
+    }
+
+    pub fn into_cursor(self) -> &'b mut Cursor<'a> {
+        self.cursor
+    }
+}
+
+impl<'a, const ENDINGS: bool> Iterator for CursorLines<'a, '_, ENDINGS> {
     type Item = (usize, usize, &'a str);
 
     fn next(&mut self) -> Option<Self::Item> {
         let line = self.cursor.line();
-        let (off, ret) = self.cursor.next_line()?;
+        let (off, ret) = if ENDINGS {
+            self.cursor.next_line_with_ending()?
+        } else {
+            self.cursor.next_line()?
+        };
         Some((off, line, ret))
     }
 }
@@ -224,6 +725,47 @@ mod tests {
         assert_eq!(cursor.lookback_char(), None);
     }
 
+    #[test]
+    fn test_new_at() {
+        let cursor = Cursor::new_at("ab\ncd", 4).unwrap();
+        assert_eq!(
+            (cursor.peek_char(), cursor.line(), cursor.column()),
+            (Some('d'), 1, 1)
+        );
+        assert_eq!(
+            Cursor::new_at("é", 1).err(),
+            Some(SeekError::NotABoundary { offset: 1 })
+        );
+
+        let mut cursor = Cursor::new_at_end("ab\ncd");
+        assert_eq!((cursor.line(), cursor.column()), (1, 2));
+        assert_eq!(cursor.prev_word(), Some((3, "cd")));
+    }
+
+    #[test]
+    fn test_remaining_and_consumed() {
+        let mut cursor = Cursor::new("key: value");
+        assert_eq!((cursor.consumed(), cursor.remaining()), ("", "key: value"));
+        cursor.take_until(" ");
+        assert_eq!((cursor.consumed(), cursor.remaining()), ("key:", " value"));
+        while cursor.next().is_some() {}
+        assert_eq!((cursor.consumed(), cursor.remaining()), ("key: value", ""));
+    }
+
+    #[test]
+    fn test_start_and_end_queries() {
+        let mut cursor = Cursor::new("añ");
+        assert!(cursor.is_at_start() && !cursor.is_at_end());
+        assert_eq!((cursor.remaining_len(), cursor.remaining_chars()), (3, 2));
+        cursor.next();
+        assert!(!cursor.is_at_start());
+        assert_eq!((cursor.remaining_len(), cursor.remaining_chars()), (2, 1));
+        cursor.next();
+        assert!(cursor.is_at_end());
+        assert_eq!(cursor.remaining_len(), 0);
+        assert!(Cursor::new("").is_at_start() && Cursor::new("").is_at_end());
+    }
+
     #[test]
     fn test_empty_string() {
         let mut cursor = Cursor::new("");
@@ -282,6 +824,115 @@ mod tests {
         assert_eq!(cursor.lookback(), Some((1, 'b')));
     }
 
+    #[test]
+    fn test_peek_n_and_lookback_n() {
+        let mut cursor = Cursor::new("a/*é*/");
+        cursor.next();
+        assert_eq!(cursor.peek_n(0), cursor.peek());
+        assert_eq!(cursor.peek_n(1), Some((2, '*')));
+        assert_eq!(cursor.peek_n(3), Some((5, '*')));
+        assert_eq!(cursor.peek_n(5), None);
+
+        let cursor = Cursor::new_at_end("a/*é*/");
+        assert_eq!(cursor.lookback_n(0), cursor.lookback());
+        assert_eq!(cursor.lookback_n(2), Some((3, 'é')));
+        assert_eq!(cursor.lookback_n(5), Some((0, 'a')));
+        assert_eq!(cursor.lookback_n(6), None);
+        assert_eq!(cursor.offset(), 7);
+    }
+
+    #[test]
+    fn test_peek_str_and_lookback_str() {
+        let mut cursor = Cursor::new("é => x");
+        cursor.seek_to_offset(3).unwrap();
+        assert_eq!(cursor.peek_str(2), Some("=>"));
+        assert_eq!(cursor.peek_str(0), Some(""));
+        assert_eq!(cursor.peek_str(4), Some("=> x"));
+        assert_eq!(cursor.peek_str(5), None);
+        assert_eq!(cursor.lookback_str(2), Some("é "));
+        assert_eq!(cursor.lookback_str(3), None);
+        assert_eq!(cursor.offset(), 3);
+    }
+
+    #[test]
+    fn test_peek_word_and_line() {
+        let mut cursor = Cursor::new("  let x\nin y");
+        assert_eq!(cursor.peek_word(), Some((2, "let")));
+        assert_eq!(cursor.peek_line(), Some((0, "  let x")));
+        assert_eq!(cursor.offset(), 0);
+        assert_eq!(cursor.peek_line(), cursor.clone().next_line());
+
+        cursor.next_line();
+        assert_eq!(cursor.peek_line(), Some((8, "in y")));
+        assert_eq!(cursor.peek_word(), cursor.next_word());
+        cursor.next_line();
+        assert_eq!((cursor.peek_line(), cursor.peek_word()), (None, None));
+    }
+
+    #[test]
+    fn test_advance_by_and_retreat_by() {
+        let mut cursor = Cursor::new("ab\nçd");
+        assert_eq!(cursor.advance_by(4), 4);
+        assert_eq!(cursor.peek(), Some((5, 'd')));
+        assert_eq!((cursor.line(), cursor.column()), (1, 1));
+        assert_eq!(cursor.advance_by(5), 1);
+        assert!(cursor.is_at_end());
+
+        assert_eq!(cursor.retreat_by(3), 3);
+        assert_eq!((cursor.offset(), cursor.line(), cursor.column()), (2, 0, 2));
+        assert_eq!(cursor.retreat_by(0), 0);
+        assert_eq!(cursor.retreat_by(9), 2);
+        assert_eq!(cursor.position(), Position::default());
+    }
+
+    #[test]
+    fn test_eat_char_and_eat_str() {
+        let mut cursor = Cursor::new("{a ->\nb}");
+        assert!(!cursor.eat_char('}'));
+        assert!(cursor.eat_char('{'));
+        assert!(cursor.eat_char('a'));
+        assert!(!cursor.eat_str("->"));
+        assert_eq!(cursor.offset(), 2);
+        assert!(cursor.eat_str(" ->\n"));
+        assert_eq!((cursor.line(), cursor.column()), (1, 0));
+        assert!(cursor.eat_str(""));
+        assert!(!cursor.eat_str("b}!"));
+        assert!(cursor.eat_str("b}"));
+        assert!(!cursor.eat_char('}'));
+    }
+
+    #[test]
+    fn test_spanned_iterators() {
+        let text = "ab  é\n\nxyz";
+        let mut cursor = Cursor::new(text);
+        let words: Vec<_> = cursor.words_spanned().collect();
+        assert_eq!(
+            words,
+            [
+                (Span::new(0, 2), "ab"),
+                (Span::new(4, 6), "é"),
+                (Span::new(8, 11), "xyz")
+            ]
+        );
+        assert!(words
+            .iter()
+            .all(|&(span, w)| &text[span.start..span.end] == w));
+
+        let mut cursor = Cursor::new(text);
+        let lines: Vec<_> = cursor
+            .lines_spanned()
+            .map(|(span, n, _)| (span, n))
+            .collect();
+        assert_eq!(
+            lines,
+            [
+                (Span::new(0, 6), 0),
+                (Span::new(7, 7), 1),
+                (Span::new(8, 11), 2)
+            ]
+        );
+    }
+
     #[test]
     fn test_bidirectional_movement() {
         let mut cursor = Cursor::new("hello");
@@ -293,6 +944,228 @@ mod tests {
         assert_eq!(cursor.next_char(), Some('h'));
     }
 
+    #[test]
+    fn test_positions_stay_absolute_after_prev() {
+        let mut cursor = Cursor::new("abc");
+
+        cursor.next();
+        cursor.next();
+        cursor.prev();
+        assert_eq!(cursor.next(), Some((1, 'b')));
+        assert_eq!(cursor.peek(), Some((2, 'c')));
+    }
+
+    #[test]
+    fn test_words_exclude_whitespace() {
+        let mut cursor = Cursor::new("  a b\n a");
+        let words: Vec<_> = cursor.words().collect();
+        assert_eq!(words, [(2, "a"), (4, "b"), (7, "a")]);
+    }
+
+    #[test]
+    fn test_prev_word() {
+        let mut cursor = Cursor::new("let  x =\n 42 ");
+        while cursor.next().is_some() {}
+        assert_eq!(cursor.prev_word(), Some((10, "42")));
+        assert_eq!(cursor.prev_word(), Some((7, "=")));
+        assert_eq!(cursor.line(), 0);
+        assert_eq!(cursor.prev_word(), Some((5, "x")));
+        assert_eq!(cursor.prev_word(), Some((0, "let")));
+        assert_eq!(cursor.prev_word(), None);
+
+        let mut cursor = Cursor::new("ab cd");
+        cursor.next();
+        assert_eq!(cursor.prev_word(), Some((0, "a")));
+    }
+
+    #[test]
+    fn test_words_rev() {
+        let mut cursor = Cursor::new(" a bc\n de ");
+        cursor.take_until("e");
+        let words: Vec<_> = cursor.words_rev().collect();
+        assert_eq!(words, [(7, "d"), (3, "bc"), (1, "a")]);
+        assert_eq!((cursor.offset, cursor.line()), (0, 0));
+    }
+
+    #[test]
+    fn test_lines_rev() {
+        let text = "fn main() {\n    let x = ;\n}\n";
+        let mut cursor = Cursor::new(text);
+        cursor.take_until(";");
+        let above: Vec<_> = cursor.lines_rev().collect();
+        assert_eq!(above, [(1, "    let x = "), (0, "fn main() {")]);
+        assert_eq!(cursor.peek(), Some((0, 'f')));
+    }
+
+    #[test]
+    fn test_prev_line() {
+        let mut cursor = Cursor::new("one\n\nthree\nfour");
+        cursor.take_until("ur");
+        assert_eq!(cursor.prev_line(), Some((11, "fo")));
+        assert_eq!(cursor.line(), 3);
+        assert_eq!(cursor.prev_line(), Some((5, "three")));
+        assert_eq!(cursor.prev_line(), Some((4, "")));
+        assert_eq!(cursor.prev_line(), Some((0, "one")));
+        assert_eq!(cursor.line(), 0);
+        assert_eq!(cursor.prev_line(), None);
+
+        let line = cursor.next_line();
+        assert_eq!(line, Some((0, "one")));
+        assert_eq!(cursor.prev_line(), line);
+        assert_eq!(cursor.peek(), Some((0, 'o')));
+    }
+
+    #[test]
+    fn test_next_line_strips_terminator() {
+        let mut cursor = Cursor::new("a\r\n\nb");
+        assert_eq!(cursor.next_line(), Some((0, "a")));
+        assert_eq!(cursor.offset(), 3);
+        assert_eq!(cursor.next_line(), Some((3, "")));
+        assert_eq!(cursor.next_line(), Some((4, "b")));
+        assert_eq!(cursor.next_line(), None);
+
+        let mut cursor = Cursor::new("a\r\n\nb");
+        assert_eq!(cursor.next_line_with_ending(), Some((0, "a\r\n")));
+        assert_eq!(cursor.next_line_with_ending(), Some((3, "\n")));
+        assert_eq!(cursor.next_line_with_ending(), Some((4, "b")));
+    }
+
+    #[test]
+    fn test_lines_with_endings() {
+        let text = "a\r\n\nb\r\nc";
+        let mut cursor = Cursor::new(text);
+        let lines: Vec<_> = cursor.lines_with_endings().collect();
+        assert_eq!(
+            lines,
+            [(0, 0, "a\r\n"), (3, 1, "\n"), (4, 2, "b\r\n"), (7, 3, "c")]
+        );
+        assert_eq!(lines.iter().map(|&(.., l)| l).collect::<String>(), text);
+
+        let mut cursor = Cursor::new(text);
+        let mut lines = cursor.lines_with_endings();
+        lines.next();
+        assert_eq!(lines.remainder(), Some("\nb\r\nc"));
+    }
+
+    #[test]
+    fn test_peek_nth_word() {
+        let mut cursor = Cursor::new("for x in xs");
+        assert_eq!(cursor.peek_nth_word(0), Some((0, "for")));
+        assert_eq!(cursor.peek_nth_word(2), Some((6, "in")));
+        assert_eq!(cursor.peek_nth_word(4), None);
+        assert_eq!(cursor.peek(), Some((0, 'f')));
+
+        cursor.next_word();
+        assert_eq!(cursor.peek_nth_word(1), Some((6, "in")));
+        let ahead: Vec<_> = cursor.peek_words().map(|(_, w)| w).collect();
+        assert_eq!(ahead, ["x", "in", "xs"]);
+        assert_eq!(cursor.next_word(), Some((4, "x")));
+    }
+
+    #[test]
+    fn test_take_lines() {
+        let mut cursor = Cursor::new("a\r\nb\nc\nd");
+        let head: Vec<_> = cursor.peek_lines(2).lines().collect();
+        assert_eq!(head, [(0, 0, "a"), (3, 1, "b")]);
+        assert_eq!(cursor.peek(), Some((0, 'a')));
+
+        let mut head = cursor.take_lines(2);
+        assert_eq!((cursor.peek(), cursor.line()), (Some((5, 'c')), 2));
+        assert_eq!(head.take_until("b"), Some((0, "a\r\n")));
+        assert_eq!(head.lines().count(), 1);
+        assert!(cursor.take_lines(0).lines().next().is_none());
+    }
+
+    #[test]
+    fn test_take_lines_past_end() {
+        let mut cursor = Cursor::new("x\ny\nz");
+        cursor.next_line();
+        let rest: Vec<_> = cursor.take_lines(5).lines().collect();
+        assert_eq!(rest, [(2, 1, "y"), (4, 2, "z")]);
+        assert_eq!((cursor.peek(), cursor.line()), (None, 2));
+    }
+
+    #[test]
+    fn test_skip_whitespace() {
+        let mut cursor = Cursor::new("a \r\n\t\r\n b");
+        cursor.next();
+        let skipped = cursor.skip_whitespace();
+        assert_eq!((skipped.span, skipped.lines), (Span::new(1, 8), 2));
+        assert_eq!((cursor.peek_char(), cursor.line()), (Some('b'), 2));
+        assert!(cursor.skip_whitespace().is_empty());
+
+        let back = cursor.skip_whitespace_back();
+        assert_eq!((back.span, back.lines), (Span::new(1, 8), 2));
+        assert_eq!((cursor.lookback_char(), cursor.line()), (Some('a'), 0));
+
+        cursor.take_until("b");
+        cursor.next();
+        let at_eof = cursor.skip_whitespace();
+        assert!(at_eof.is_empty());
+        assert_eq!(at_eof.span, Span::new(9, 9));
+    }
+
+    #[test]
+    fn test_remainder() {
+        let mut cursor = Cursor::new("  one two\n three  ");
+        let mut words = cursor.words();
+        assert_eq!(words.remainder(), Some("one two\n three  "));
+        words.next();
+        assert_eq!(words.remainder(), Some("two\n three  "));
+        words.next();
+        assert_eq!(words.remainder(), Some("three  "));
+        let mut words = cursor.words_with_lines();
+        assert_eq!(words.next(), Some((11, 1, "three")));
+        assert_eq!(words.remainder(), None);
+        assert_eq!(words.into_cursor().peek(), None);
+
+        let mut cursor = Cursor::new("a\n\nb");
+        let mut lines = cursor.lines();
+        lines.next();
+        assert_eq!(lines.remainder(), Some("\nb"));
+        lines.next();
+        assert_eq!(lines.remainder(), Some("b"));
+        lines.next();
+        assert_eq!(lines.remainder(), None);
+    }
+
+    #[test]
+    fn test_column_tracking() {
+        let mut cursor = Cursor::new("ab\ncdé\n\nf");
+        let mut seen = Vec::new();
+        while cursor.next().is_some() {
+            seen.push((cursor.line(), cursor.column()));
+        }
+        assert_eq!(
+            seen,
+            [
+                (0, 1),
+                (0, 2),
+                (1, 0),
+                (1, 1),
+                (1, 2),
+                (1, 3),
+                (2, 0),
+                (3, 0),
+                (3, 1)
+            ]
+        );
+        while cursor.prev().is_some() {
+            seen.pop();
+            let expected = seen.last().copied().unwrap_or((0, 0));
+            assert_eq!((cursor.line(), cursor.column()), expected);
+        }
+
+        cursor.take_until("é");
+        assert_eq!(cursor.column(), 2);
+        cursor.prev_line();
+        assert_eq!(cursor.column(), 0);
+        cursor.take_until("f");
+        assert_eq!((cursor.line(), cursor.column()), (3, 0));
+        cursor.skip_whitespace_back();
+        assert_eq!((cursor.line(), cursor.column()), (1, 3));
+    }
+
     #[test]
     fn test_unicode() {
         let mut cursor = Cursor::new("hello 👋 world");
@@ -0,0 +1,186 @@
+use std::sync::Arc;
+
+use crate::{Cursor, LineTerminator};
+
+/// The start offset of every line of a string, for looking up lines by
+/// binary search instead of scanning; see [`Cursor::with_line_index`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineIndex {
+    /// Sorted, starting with 0; one entry per line.
+    starts: Vec<usize>,
+    /// Byte length of the indexed text.
+    len: usize,
+    terminator: LineTerminator,
+}
+
+impl LineIndex {
+    /// Indexes the lines of `text`, which end at `\n`.
+    pub fn new(text: &str) -> Self {
+        Self::with_terminator(text, LineTerminator::Lf)
+    }
+
+    /// Indexes the lines of `text`, which end where `terminator` says.
+    pub fn with_terminator(text: &str, terminator: LineTerminator) -> Self {
+        let mut starts = vec![0];
+        while let Some(next) = terminator.nth_after(text, starts[starts.len() - 1], 0) {
+            starts.push(next);
+        }
+        Self {
+            starts,
+            len: text.len(),
+            terminator,
+        }
+    }
+
+    /// The number of lines, which is one more than the number of line
+    /// breaks.
+    pub fn line_count(&self) -> usize {
+        self.starts.len()
+    }
+
+    /// Byte offset where the zero-based line `line` starts.
+    pub fn line_start(&self, line: usize) -> Option<usize> {
+        self.starts.get(line).copied()
+    }
+
+    /// The zero-based line containing the byte offset `offset`. Offsets past
+    /// the end are on the last line.
+    pub fn line_of(&self, offset: usize) -> usize {
+        self.starts.partition_point(|&start| start <= offset) - 1
+    }
+
+    pub const fn terminator(&self) -> LineTerminator {
+        self.terminator
+    }
+}
+
+impl<'a> Cursor<'a> {
+    /// Builds a [`LineIndex`] of the input, so that seeking to a line and
+    /// finding where lines start no longer scan the text in between. Worth
+    /// it when the cursor jumps around a large input.
+    ///
+    /// Clones of the cursor share the index rather than copying it.
+    pub fn with_line_index(mut self) -> Self {
+        let index = LineIndex::with_terminator(self.data, self.terminator);
+        self.line_index = Some(Arc::new(index));
+        self
+    }
+
+    /// Uses `index`, built for the same input, so that cursors created
+    /// separately over one text can share a single index. The cursor takes
+    /// on the index's line terminator.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` was built for a text of a different length.
+    pub fn with_shared_line_index(mut self, index: Arc<LineIndex>) -> Self {
+        assert_eq!(
+            index.len,
+            self.data.len(),
+            "line index was built for a different text"
+        );
+        self.line_index = None;
+        let mut cursor = self.with_line_terminator(index.terminator);
+        cursor.line_index = Some(index);
+        cursor
+    }
+
+    pub fn line_index(&self) -> Option<&Arc<LineIndex>> {
+        self.line_index.as_ref()
+    }
+
+    /// The line containing `offset`.
+    pub(crate) fn line_of(&self, offset: usize) -> usize {
+        match &self.line_index {
+            Some(index) => index.line_of(offset),
+            None if offset >= self.offset => {
+                self.line + self.terminator.count(self.data, self.offset, offset)
+            }
+            None => self.line - self.terminator.count(self.data, offset, self.offset),
+        }
+    }
+
+    /// Byte offset where the line containing `offset` starts.
+    pub(crate) fn line_start_at(&self, offset: usize) -> usize {
+        match &self.line_index {
+            Some(index) => index.starts[index.line_of(offset)],
+            None => self.terminator.line_start(self.data, offset),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::XorShift;
+
+    #[test]
+    fn test_line_index() {
+        let index = LineIndex::new("ab\n\ncd\n");
+        assert_eq!(index.line_count(), 4);
+        assert_eq!(
+            (0..4)
+                .map(|line| index.line_start(line))
+                .collect::<Vec<_>>(),
+            [Some(0), Some(3), Some(4), Some(7)]
+        );
+        assert_eq!(index.line_start(4), None);
+        let lines: Vec<_> = (0..9).map(|offset| index.line_of(offset)).collect();
+        assert_eq!(lines, [0, 0, 0, 1, 2, 2, 2, 3, 3]);
+
+        let index = LineIndex::with_terminator("a\r\nb\rc", LineTerminator::Ascii);
+        assert_eq!(index.starts, [0, 3, 5]);
+        assert_eq!(index.line_of(2), 0);
+    }
+
+    #[test]
+    fn test_shared_line_index() {
+        let text = "a\r\nb\nc";
+        let cursor = Cursor::new(text)
+            .with_line_terminator(LineTerminator::Ascii)
+            .with_line_index();
+        let index = cursor.line_index().unwrap();
+        let mut clone = cursor.clone();
+        assert!(Arc::ptr_eq(index, clone.line_index().unwrap()));
+        assert_eq!(clone.seek_to_line(2), Ok(()));
+
+        let mut other = Cursor::new_at_end(text).with_shared_line_index(index.clone());
+        assert!(Arc::ptr_eq(index, other.line_index().unwrap()));
+        assert_eq!(other.line_terminator(), LineTerminator::Ascii);
+        assert_eq!((other.line(), other.column()), (2, 1));
+        assert_eq!(other.seek_to_line(1), Ok(()));
+        assert_eq!(other.offset(), 3);
+        assert_eq!(Arc::strong_count(index), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "different text")]
+    fn test_shared_line_index_for_other_text() {
+        let index = Arc::new(LineIndex::new("abc"));
+        let _ = Cursor::new("abcd").with_shared_line_index(index);
+    }
+
+    #[test]
+    fn test_indexed_cursor_agrees() {
+        let mut rng = XorShift(7);
+        for _ in 0..200 {
+            let text = rng.string(&['a', 'é', '\n', '\r'], 0, 30);
+            let mut plain = Cursor::new(&text).with_line_terminator(LineTerminator::Ascii);
+            let mut indexed = plain.clone().with_line_index();
+            for _ in 0..10 {
+                let line = rng.next() as usize % 6;
+                assert_eq!(indexed.seek_to_line(line), plain.seek_to_line(line));
+                assert_eq!(indexed.position(), plain.position());
+                let steps = rng.next() as usize % 8;
+                if rng.next().is_multiple_of(2) {
+                    indexed.advance_by(steps);
+                    plain.advance_by(steps);
+                } else {
+                    indexed.retreat_by(steps);
+                    plain.retreat_by(steps);
+                }
+                assert_eq!(indexed.position(), plain.position());
+            }
+        }
+    }
+}
@@ -0,0 +1,93 @@
+use logos::Logos;
+
+use crate::{Cursor, Position, Span};
+
+/// Iterator over the tokens of a `logos` lexer; see [`Cursor::logos`].
+pub struct LogosTokens<'a, 'b, T: Logos<'a>> {
+    cursor: &'b mut Cursor<'a>,
+    lexer: logos::Lexer<'a, T>,
+    base: usize,
+}
+
+impl<'a> Cursor<'a> {
+    /// Runs the `logos` lexer for `T` over the remaining input, yielding each
+    /// token with its span and the position where it starts. The cursor
+    /// follows the lexer, so its line and column stay in sync and are cheap
+    /// to keep up to date.
+    pub fn logos<T>(&mut self) -> LogosTokens<'a, '_, T>
+    where
+        T: Logos<'a, Source = str>,
+        T::Extras: Default,
+    {
+        let base = self.offset;
+        LogosTokens {
+            lexer: T::lexer(self.forward()),
+            cursor: self,
+            base,
+        }
+    }
+}
+
+impl<'a, T: Logos<'a, Source = str>> LogosTokens<'a, '_, T> {
+    /// The lexer's extras, as set by its callbacks.
+    pub fn extras(&self) -> &T::Extras {
+        &self.lexer.extras
+    }
+}
+
+impl<'a, T: Logos<'a, Source = str>> Iterator for LogosTokens<'a, '_, T> {
+    type Item = (Result<T, T::Error>, Span, Position);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Some(token) = self.lexer.next() else {
+            self.cursor.advance_to(self.cursor.data.len());
+            return None;
+        };
+        let range = self.lexer.span();
+        let span = Span::new(self.base + range.start, self.base + range.end);
+        self.cursor.advance_to(span.start);
+        let position = Cursor::position(self.cursor);
+        self.cursor.advance_to(span.end);
+        Some((token, span, position))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Logos, Debug, Clone, Copy, PartialEq, Eq)]
+    #[logos(skip r"[ \t\n]+")]
+    enum Tok {
+        #[token("let")]
+        Let,
+        #[regex("[a-z]+")]
+        Ident,
+        #[regex("[0-9]+")]
+        Int,
+        #[token("=")]
+        Eq,
+    }
+
+    #[test]
+    fn test_logos_positions() {
+        let mut cursor = Cursor::new("skip\nlet x =\n  42 ? \n");
+        cursor.next_line();
+        let tokens: Vec<_> = cursor
+            .logos::<Tok>()
+            .map(|(tok, span, pos)| (tok, span.start, pos.line, pos.column))
+            .collect();
+        assert_eq!(
+            tokens,
+            [
+                (Ok(Tok::Let), 5, 1, 0),
+                (Ok(Tok::Ident), 9, 1, 4),
+                (Ok(Tok::Eq), 11, 1, 6),
+                (Ok(Tok::Int), 15, 2, 2),
+                (Err(()), 18, 2, 5),
+            ]
+        );
+        assert!(cursor.is_at_end());
+        assert_eq!((cursor.line(), cursor.column()), (3, 0));
+    }
+}
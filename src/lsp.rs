@@ -0,0 +1,177 @@
+use lsp_types::{Position, Range, TextDocumentContentChangeEvent};
+
+use crate::{Checkpoint, ColumnUnit, Cursor, Edit, Span};
+
+impl Cursor<'_> {
+    /// The cursor's position as an LSP position, whose column counts UTF-16
+    /// code units whatever the cursor's [`ColumnUnit`]. Lines end where the
+    /// cursor's line terminator says.
+    pub fn to_lsp_position(&self) -> Position {
+        match self.column_unit {
+            ColumnUnit::Utf16 => Position::new(self.line as u32, self.column as u32),
+            _ => self.lsp_position_of(self.offset),
+        }
+    }
+
+    /// The LSP position of the byte offset `offset`, clamped and rounded
+    /// like [`Cursor::position_of`].
+    pub fn lsp_position_of(&self, offset: usize) -> Position {
+        let mut offset = offset.min(self.data.len());
+        while !self.data.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let line_start = self.line_start_at(offset);
+        let character = ColumnUnit::Utf16.width(&self.data[line_start..offset]);
+        Position::new(self.line_of(offset) as u32, character as u32)
+    }
+
+    /// The byte offset of an LSP position, or `None` if its line is past the
+    /// end. As LSP specifies, a character past the end of the line means the
+    /// end of the line.
+    pub fn from_lsp_position(&self, position: Position) -> Option<usize> {
+        let start = self.line_start(position.line as usize).ok()?;
+        let column = position.character as usize;
+        let offset = match self.column_offset_in(start, column, ColumnUnit::Utf16) {
+            Ok(offset) => offset,
+            Err(len) => self.column_offset_in(start, len, ColumnUnit::Utf16).ok()?,
+        };
+        Some(offset)
+    }
+
+    /// The LSP range covering `span`.
+    pub fn lsp_range(&self, span: Span) -> Range {
+        Range::new(
+            self.lsp_position_of(span.start),
+            self.lsp_position_of(span.end),
+        )
+    }
+
+    /// Follows the cursor's input through `changes`, as sent with
+    /// `textDocument/didChange`, returning a cursor over `text`, the
+    /// document after them. The cursor's position, its marks and
+    /// `checkpoints` move with the text around them: text inserted before a
+    /// position pushes it along, and a position inside replaced text moves
+    /// to the start of the replacement. A change without a range replaces
+    /// the whole document.
+    ///
+    /// Each change's range is resolved against the document as the changes
+    /// before it left it, as LSP specifies.
+    pub fn apply_lsp_changes<'b>(
+        &self,
+        text: &'b str,
+        changes: &[TextDocumentContentChangeEvent],
+        checkpoints: &mut [Checkpoint],
+    ) -> Cursor<'b> {
+        let mut document = self.data.to_owned();
+        let mut edits = Vec::with_capacity(changes.len());
+        for change in changes {
+            let range = match change.range {
+                Some(range) => {
+                    let cursor = Cursor::new(&document).with_line_terminator(self.terminator);
+                    let start = cursor.from_lsp_position(range.start);
+                    let start = start.unwrap_or(document.len());
+                    let end = cursor.from_lsp_position(range.end);
+                    start..end.unwrap_or(document.len()).max(start)
+                }
+                None => 0..document.len(),
+            };
+            document.replace_range(range.clone(), &change.text);
+            edits.push(Edit::new(range, change.text.len()));
+        }
+        debug_assert_eq!(document, text, "changes don't produce `text`");
+        let map = |offset| {
+            edits
+                .iter()
+                .fold(offset, |offset, edit| edit.map_offset(offset))
+        };
+        self.remapped(text, map, checkpoints)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::LineTerminator;
+
+    fn change(start: (u32, u32), end: (u32, u32), text: &str) -> TextDocumentContentChangeEvent {
+        TextDocumentContentChangeEvent {
+            range: Some(Range::new(
+                Position::new(start.0, start.1),
+                Position::new(end.0, end.1),
+            )),
+            range_length: None,
+            text: text.to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_lsp_positions() {
+        let text = "let \u{1f600} = 1;\r\n  x\u{e9}y";
+        let mut cursor = Cursor::new(text).with_line_terminator(LineTerminator::Ascii);
+        cursor.take_until("=");
+        assert_eq!(cursor.to_lsp_position(), Position::new(0, 7));
+        assert_eq!(
+            cursor
+                .clone()
+                .with_column_unit(ColumnUnit::Utf16)
+                .to_lsp_position(),
+            Position::new(0, 7)
+        );
+        assert_eq!(cursor.from_lsp_position(Position::new(0, 7)), Some(9));
+        assert_eq!(
+            cursor.from_lsp_position(Position::new(1, 99)),
+            Some(text.len())
+        );
+        assert_eq!(cursor.from_lsp_position(Position::new(0, 99)), Some(13));
+        assert_eq!(cursor.from_lsp_position(Position::new(2, 0)), None);
+
+        let y = text.find('y').unwrap();
+        let range = cursor.lsp_range(Span::new(4, y));
+        assert_eq!(range, Range::new(Position::new(0, 4), Position::new(1, 4)));
+        assert_eq!(cursor.from_lsp_position(range.end), Some(y));
+    }
+
+    #[test]
+    fn test_apply_lsp_changes() {
+        let before = "fn main() {\n    let x = 10;\n}\n";
+        let after = "fn main() {\n    let mut y = 7;\n}\n";
+        let mut cursor = Cursor::new(before);
+        cursor.take_until("x");
+        cursor.set_mark("name");
+        let name = cursor.checkpoint();
+        cursor.take_until("1");
+        let value = cursor.checkpoint();
+        cursor.take_until("}");
+
+        let changes = [
+            change((1, 8), (1, 8), "mut "),
+            change((1, 12), (1, 13), "y"),
+            change((1, 16), (1, 18), "7"),
+        ];
+        let mut checkpoints = [name, value];
+        let mut cursor = cursor.apply_lsp_changes(after, &changes, &mut checkpoints);
+        assert_eq!((cursor.line(), cursor.column()), (2, 0));
+        assert_eq!(cursor.peek_char(), Some('}'));
+        cursor.restore(checkpoints[0]);
+        assert_eq!(cursor.remaining(), "y = 7;\n}\n");
+        assert_eq!(cursor.column(), 12);
+        assert!(cursor.goto_mark("name"));
+        assert_eq!(cursor.to_lsp_position(), Position::new(1, 12));
+        cursor.restore(checkpoints[1]);
+        assert_eq!(cursor.remaining(), "7;\n}\n");
+    }
+
+    #[test]
+    fn test_full_document_change() {
+        let cursor = Cursor::new_at_end("old").with_line_index();
+        let change = TextDocumentContentChangeEvent {
+            range: None,
+            range_length: None,
+            text: "new\ntext".to_owned(),
+        };
+        let cursor = cursor.apply_lsp_changes("new\ntext", &[change], &mut []);
+        assert!(cursor.is_at_end());
+        assert_eq!(cursor.line_index().unwrap().line_count(), 2);
+        assert_eq!((cursor.line(), cursor.column()), (1, 4));
+    }
+}
@@ -0,0 +1,138 @@
+use std::str::{CharIndices, Chars, FromStr};
+
+use nom::{Compare, CompareResult, FindSubstring, Input, Needed, Offset, ParseTo};
+
+use crate::Cursor;
+
+impl<'a> Cursor<'a> {
+    /// The next `len` bytes as a cursor of their own, starting at this
+    /// cursor's position.
+    fn prefix(&self, len: usize) -> Self {
+        Self {
+            data: &self.data[..self.offset + len],
+            marks: Vec::new(),
+            ..self.clone()
+        }
+    }
+
+    fn suffix(&self, len: usize) -> Self {
+        let mut rest = Self {
+            marks: Vec::new(),
+            ..self.clone()
+        };
+        rest.advance_to(self.offset + len);
+        rest
+    }
+}
+
+/// Input is what remains after the cursor. Taking a prefix gives a cursor
+/// that ends where the prefix does but keeps its line and column, so the
+/// results of parsers carry their positions too.
+impl<'a> Input for Cursor<'a> {
+    type Item = char;
+    type Iter = Chars<'a>;
+    type IterIndices = CharIndices<'a>;
+
+    fn input_len(&self) -> usize {
+        self.forward().len()
+    }
+
+    fn take(&self, index: usize) -> Self {
+        self.prefix(index)
+    }
+
+    fn take_from(&self, index: usize) -> Self {
+        self.suffix(index)
+    }
+
+    fn take_split(&self, index: usize) -> (Self, Self) {
+        (self.suffix(index), self.prefix(index))
+    }
+
+    fn position<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: Fn(Self::Item) -> bool,
+    {
+        self.forward().find(predicate)
+    }
+
+    fn iter_elements(&self) -> Self::Iter {
+        self.forward().chars()
+    }
+
+    fn iter_indices(&self) -> Self::IterIndices {
+        self.forward().char_indices()
+    }
+
+    fn slice_index(&self, count: usize) -> Result<usize, Needed> {
+        self.forward().slice_index(count)
+    }
+}
+
+impl Offset for Cursor<'_> {
+    fn offset(&self, second: &Self) -> usize {
+        second.offset - self.offset
+    }
+}
+
+impl<'b> Compare<&'b str> for Cursor<'_> {
+    fn compare(&self, t: &'b str) -> CompareResult {
+        self.forward().compare(t)
+    }
+
+    fn compare_no_case(&self, t: &'b str) -> CompareResult {
+        self.forward().compare_no_case(t)
+    }
+}
+
+impl<'b> FindSubstring<&'b str> for Cursor<'_> {
+    fn find_substring(&self, substr: &'b str) -> Option<usize> {
+        self.forward().find(substr)
+    }
+}
+
+impl<R: FromStr> ParseTo<R> for Cursor<'_> {
+    fn parse_to(&self) -> Option<R> {
+        self.forward().parse().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::bytes::complete::tag;
+    use nom::character::complete::{alpha1, digit1, multispace0};
+    use nom::combinator::map_res;
+    use nom::sequence::{preceded, separated_pair};
+    use nom::{IResult, Parser};
+
+    use super::*;
+
+    // nom errors carry the input, and a cursor is a lot larger than a `&str`.
+    #[allow(clippy::result_large_err)]
+    fn assignment(input: Cursor) -> IResult<Cursor, (Cursor, u32)> {
+        let value = map_res(digit1, |d: Cursor| d.forward().parse::<u32>());
+        let name = preceded(multispace0, alpha1);
+        let eq = preceded(multispace0, tag("="));
+        separated_pair(name, eq, preceded(multispace0, value)).parse(input)
+    }
+
+    #[test]
+    fn test_parse_cursor() {
+        let input = Cursor::new("\n  width = 42;");
+        let (rest, (name, value)) = assignment(input).unwrap();
+        assert_eq!((name.forward(), value), ("width", 42));
+        assert_eq!((name.line(), name.column()), (1, 2));
+        assert_eq!(rest.forward(), ";");
+        assert_eq!((rest.line(), rest.column()), (1, 12));
+    }
+
+    #[test]
+    fn test_error_position() {
+        let err = assignment(Cursor::new("a =\n  b")).unwrap_err();
+        let nom::Err::Error(err) = err else {
+            panic!("expected a recoverable error, got {err:?}");
+        };
+        assert_eq!(err.code, nom::error::ErrorKind::Digit);
+        assert_eq!(err.input.position().to_string(), "2:3");
+    }
+}
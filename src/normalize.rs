@@ -0,0 +1,76 @@
+use unicode_normalization::UnicodeNormalization;
+
+use crate::Cursor;
+
+/// Byte length of the prefix of `haystack` that is canonically equivalent
+/// to `needle`, so that `é` matches `e` and a combining acute. The prefix
+/// can't end before a char that would combine with it.
+pub(crate) fn prefix_len_nfc(haystack: &str, needle: &str) -> Option<usize> {
+    // Decomposed lengths add up, so they pin down the one candidate prefix.
+    let wanted = needle.nfd().count();
+    let (mut decomposed, mut end) = (0, 0);
+    for c in haystack.chars() {
+        if decomposed >= wanted {
+            break;
+        }
+        decomposed += c.nfd().count();
+        end += c.len_utf8();
+    }
+    let prefix = &haystack[..end];
+    if decomposed != wanted || !prefix.nfc().eq(needle.nfc()) {
+        return None;
+    }
+    let Some(next) = haystack[end..].chars().next() else {
+        return Some(end);
+    };
+    let extended: String = haystack[..end + next.len_utf8()].nfc().collect();
+    let stable = extended.starts_with(&prefix.nfc().collect::<String>());
+    stable.then_some(end)
+}
+
+impl<'a> Cursor<'a> {
+    /// Compares strings under Unicode normalization (NFC) in
+    /// [`Cursor::eat_str`], [`Cursor::starts_with`] and
+    /// [`Cursor::followed_by`], so composed and decomposed forms of the same
+    /// text match. Other needles are unaffected.
+    pub fn with_nfc_matching(mut self, nfc: bool) -> Self {
+        self.nfc = nfc;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COMPOSED: &str = "caf\u{e9}";
+    const DECOMPOSED: &str = "cafe\u{301}";
+
+    #[test]
+    fn test_nfc_matching() {
+        let mut cursor = Cursor::new("cafe\u{301} au lait").with_nfc_matching(true);
+        assert!(cursor.starts_with(COMPOSED));
+        assert!(cursor.starts_with(DECOMPOSED));
+        assert!(!cursor.starts_with("cafe"));
+        assert!(cursor.eat_str(COMPOSED));
+        assert_eq!(cursor.offset(), DECOMPOSED.len());
+        assert_eq!(cursor.column(), 5);
+
+        let mut cursor = Cursor::new(COMPOSED).with_nfc_matching(true);
+        assert!(cursor.eat_str(DECOMPOSED));
+        assert!(cursor.is_at_end());
+        assert!(Cursor::new("").with_nfc_matching(true).starts_with(""));
+        // A match can't stop before a mark that combines with its last char.
+        let cursor = Cursor::new("e\u{301}x").with_nfc_matching(true);
+        assert!(!cursor.starts_with("e"));
+        assert!(cursor.starts_with("\u{e9}x"));
+    }
+
+    #[test]
+    fn test_off_by_default() {
+        assert!(!Cursor::new(DECOMPOSED).starts_with(COMPOSED));
+        let cursor = Cursor::new(DECOMPOSED).with_nfc_matching(true);
+        assert!(cursor.clone().with_nfc_matching(false).starts_with("cafe"));
+        assert!(cursor.starts_with(['c']));
+    }
+}
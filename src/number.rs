@@ -0,0 +1,294 @@
+use std::ops::Range;
+
+use crate::{Cursor, Span};
+
+/// The value of a numeric literal found by [`Cursor::numbers`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NumberLit {
+    Integer(i128),
+    Float(f64),
+}
+
+fn sign_len(s: &str) -> usize {
+    usize::from(s.starts_with(['+', '-']))
+}
+
+/// Length of the digits at the start of `s`, allowing `_` separators between
+/// them but not before the first or after the last.
+fn digits_len(s: &str, radix: u32) -> usize {
+    let mut end = 0;
+    for (i, b) in s.bytes().enumerate() {
+        if char::from(b).is_digit(radix) {
+            end = i + 1;
+        } else if b != b'_' || end == 0 {
+            break;
+        }
+    }
+    end
+}
+
+fn radix_prefix(s: &str) -> Option<u32> {
+    let prefix = s.get(..2)?;
+    match prefix.to_ascii_lowercase().as_str() {
+        "0x" => Some(16),
+        "0o" => Some(8),
+        "0b" => Some(2),
+        _ => None,
+    }
+}
+
+/// Byte length of the longest integer literal at the start of `s`: an
+/// optional sign, an optional `0x`/`0o`/`0b` prefix, and digits.
+pub(crate) fn scan_integer(s: &str) -> Option<usize> {
+    let sign = sign_len(s);
+    let rest = &s[sign..];
+    if let Some(radix) = radix_prefix(rest) {
+        let len = digits_len(&rest[2..], radix);
+        if len > 0 {
+            return Some(sign + 2 + len);
+        }
+    }
+    let len = digits_len(rest, 10);
+    (len > 0).then_some(sign + len)
+}
+
+/// Byte length of the longest float literal at the start of `s`. A float
+/// needs a fractional part (`1.5`), an exponent (`1e3`), or both.
+pub(crate) fn scan_float(s: &str) -> Option<usize> {
+    let mut end = sign_len(s);
+    let int = digits_len(&s[end..], 10);
+    if int == 0 {
+        return None;
+    }
+    end += int;
+
+    let mut is_float = false;
+    if s[end..].starts_with('.') {
+        let frac = digits_len(&s[end + 1..], 10);
+        if frac > 0 {
+            end += 1 + frac;
+            is_float = true;
+        }
+    }
+    if s[end..].starts_with(['e', 'E']) {
+        let exp_start = end + 1;
+        let exp_sign = sign_len(&s[exp_start..]);
+        let exp = digits_len(&s[exp_start + exp_sign..], 10);
+        if exp > 0 {
+            end = exp_start + exp_sign + exp;
+            is_float = true;
+        }
+    }
+    is_float.then_some(end)
+}
+
+impl NumberLit {
+    /// The value of a literal accepted by [`scan_integer`] or [`scan_float`].
+    /// Decimal integers too large for `i128` become floats.
+    pub(crate) fn parse(text: &str, float: bool) -> Option<Self> {
+        let clean: String = text.chars().filter(|&c| c != '_').collect();
+        if float {
+            return clean.parse().ok().map(Self::Float);
+        }
+        let (negative, unsigned) = match clean.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, clean.strip_prefix('+').unwrap_or(&clean)),
+        };
+        let value = match radix_prefix(unsigned) {
+            Some(radix) => i128::from_str_radix(&unsigned[2..], radix).ok()?,
+            None => match unsigned.parse::<i128>() {
+                Ok(value) => value,
+                Err(_) => return clean.parse().ok().map(Self::Float),
+            },
+        };
+        Some(Self::Integer(if negative { -value } else { value }))
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Iterator over the numeric literals in the remaining input; see
+/// [`Cursor::numbers`].
+pub struct Numbers<'a, 'b> {
+    cursor: &'b mut Cursor<'a>,
+    boundary: bool,
+}
+
+impl Numbers<'_, '_> {
+    /// Whether a number must start at a word boundary (the default), so that
+    /// `abc123` contains no number.
+    pub fn require_boundary(mut self, boundary: bool) -> Self {
+        self.boundary = boundary;
+        self
+    }
+}
+
+impl<'a> Cursor<'a> {
+    /// Scans the remaining input for integer and float literals, yielding
+    /// each one's span and value and skipping everything in between.
+    ///
+    /// A `+` or `-` belongs to the number only when the sign itself starts a
+    /// word and is directly followed by a digit: `a -5` and `(-5)` yield -5,
+    /// while `a-5` and `1 - 5` yield 5.
+    pub fn numbers(&mut self) -> Numbers<'a, '_> {
+        Numbers {
+            cursor: self,
+            boundary: true,
+        }
+    }
+}
+
+impl<'a> Cursor<'a> {
+    /// Consumes the longest integer literal at the cursor: an optional sign,
+    /// an optional `0x`, `0o` or `0b` prefix, and digits with optional `_`
+    /// separators between them. `0x` without hex digits after it scans as
+    /// just the `0`.
+    pub fn next_integer(&mut self) -> Option<(Span, &'a str)> {
+        let len = scan_integer(self.forward())?;
+        Some(self.take_number(len))
+    }
+
+    /// Consumes the longest float literal at the cursor: an optional sign,
+    /// decimal digits, and a fractional part, an exponent, or both. Plain
+    /// integers are left for [`Cursor::next_integer`].
+    pub fn next_float(&mut self) -> Option<(Span, &'a str)> {
+        let len = scan_float(self.forward())?;
+        Some(self.take_number(len))
+    }
+
+    fn take_number(&mut self, len: usize) -> (Span, &'a str) {
+        let start = self.offset;
+        self.advance_to(start + len);
+        (
+            Span::new(start, self.offset),
+            &self.data[start..self.offset],
+        )
+    }
+}
+
+impl Iterator for Numbers<'_, '_> {
+    type Item = (Range<usize>, NumberLit);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (start, c) = self.cursor.peek()?;
+            let after_word = self.cursor.lookback_char().is_some_and(is_word_char);
+            let digit = c.is_ascii_digit();
+            let candidate = if c == '+' || c == '-' {
+                !after_word && self.cursor.forward()[1..].starts_with(|c: char| c.is_ascii_digit())
+            } else {
+                digit
+            };
+            if candidate {
+                let rest = self.cursor.forward();
+                let (len, float) = match scan_float(rest) {
+                    Some(len) => (len, true),
+                    None => (scan_integer(rest).unwrap_or(c.len_utf8()), false),
+                };
+                let end = start + len;
+                let value = NumberLit::parse(&rest[..len], float);
+                self.cursor.advance_to(end);
+                if digit && self.boundary && after_word {
+                    // The digits are inside a word such as `v1.2`; skip the
+                    // rest of it rather than finding a number further in.
+                    self.cursor.consume_while(is_word_char);
+                    continue;
+                }
+                if let Some(value) = value {
+                    return Some((start..end, value));
+                }
+                continue;
+            }
+            self.cursor.next();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbers(input: &str) -> Vec<(Range<usize>, NumberLit)> {
+        Cursor::new(input).numbers().collect()
+    }
+
+    #[test]
+    fn test_scanners() {
+        assert_eq!(scan_integer("-0x1F_ff+"), Some(8));
+        assert_eq!(scan_integer("0b102"), Some(4));
+        assert_eq!(scan_integer("0xg"), Some(1));
+        assert_eq!(scan_integer("1_000_"), Some(5));
+        assert_eq!(scan_integer("_1"), None);
+        assert_eq!(scan_float("1.5e-3x"), Some(6));
+        assert_eq!(scan_float("2E8"), Some(3));
+        assert_eq!(scan_float("1."), None);
+        assert_eq!(scan_float("1.e5"), None);
+        assert_eq!(scan_float("12"), None);
+    }
+
+    #[test]
+    fn test_next_integer_and_float() {
+        let mut cursor = Cursor::new("-0x1F_ff+2.5e3 1.x");
+        assert_eq!(cursor.next_float(), None);
+        assert_eq!(cursor.next_integer(), Some((Span::new(0, 8), "-0x1F_ff")));
+        assert_eq!(cursor.next_float(), Some((Span::new(8, 14), "+2.5e3")));
+        cursor.next();
+        assert_eq!(cursor.next_float(), None);
+        assert_eq!(cursor.next_integer(), Some((Span::new(15, 16), "1")));
+        assert_eq!(cursor.next_integer(), None);
+        assert_eq!(cursor.peek_char(), Some('.'));
+    }
+
+    #[test]
+    fn test_numbers_with_spans() {
+        use NumberLit::*;
+        assert_eq!(
+            numbers("took 12ms, 0.5s total; code=0x1f"),
+            [
+                (5..7, Integer(12)),
+                (11..14, Float(0.5)),
+                (28..32, Integer(31))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_numbers_require_word_boundary() {
+        use NumberLit::*;
+        assert_eq!(numbers("abc123 x_9 v2"), []);
+        assert_eq!(numbers("x_9.5 v1.2 abc1def 7"), [(19..20, Integer(7))]);
+        let mut cursor = Cursor::new("abc123");
+        let anywhere: Vec<_> = cursor.numbers().require_boundary(false).collect();
+        assert_eq!(anywhere, [(3..6, Integer(123))]);
+    }
+
+    #[test]
+    fn test_numbers_sign_rule() {
+        use NumberLit::*;
+        assert_eq!(numbers("a -5"), [(2..4, Integer(-5))]);
+        assert_eq!(numbers("a-5"), [(2..3, Integer(5))]);
+        assert_eq!(numbers("(-2.5)"), [(1..5, Float(-2.5))]);
+        assert_eq!(numbers("1 - 5"), [(0..1, Integer(1)), (4..5, Integer(5))]);
+        assert_eq!(numbers("3-5"), [(0..1, Integer(3)), (2..3, Integer(5))]);
+    }
+
+    #[test]
+    fn test_numbers_keep_line() {
+        let mut cursor = Cursor::new("a 1\nb\nc 2 d");
+        let mut lines = Vec::new();
+        while let Some((span, _)) = cursor.numbers().next() {
+            lines.push((span.start, cursor.line()));
+        }
+        assert_eq!(lines, [(2, 0), (8, 2)]);
+        assert_eq!((cursor.next(), cursor.line()), (None, 2));
+    }
+
+    #[test]
+    fn test_integer_overflow_becomes_float() {
+        let huge = "1".repeat(50);
+        let found = numbers(&huge);
+        assert!(matches!(found[..], [(_, NumberLit::Float(v))] if v > 1e49));
+    }
+}
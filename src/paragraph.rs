@@ -0,0 +1,48 @@
+use crate::Cursor;
+
+/// Iterator over the remaining paragraphs; see [`Cursor::paragraphs`].
+pub struct CursorParagraphs<'a, 'b> {
+    cursor: &'b mut Cursor<'a>,
+}
+
+impl<'a> Cursor<'a> {
+    /// Iterates over runs of non-blank lines, yielding each run's start
+    /// offset, starting line number, and text without the final terminator.
+    /// Lines of only whitespace separate paragraphs and are skipped.
+    pub fn paragraphs(&mut self) -> CursorParagraphs<'a, '_> {
+        CursorParagraphs { cursor: self }
+    }
+}
+
+impl<'a> Iterator for CursorParagraphs<'a, '_> {
+    type Item = (usize, usize, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let is_blank = |line: &str| line.trim().is_empty();
+        while is_blank(self.cursor.peek_line()?.1) {
+            self.cursor.next_line();
+        }
+        let (start, line) = (self.cursor.offset, self.cursor.line);
+        let mut end = start;
+        while let Some((at, text)) = self.cursor.peek_line().filter(|(_, l)| !is_blank(l)) {
+            end = at + text.len();
+            self.cursor.next_line();
+        }
+        Some((start, line, &self.cursor.data[start..end]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paragraphs() {
+        let text = "\n  \nfirst line\nsecond\r\n\t\n\nlast\n";
+        let mut cursor = Cursor::new(text);
+        let paragraphs: Vec<_> = cursor.paragraphs().collect();
+        assert_eq!(paragraphs, [(4, 2, "first line\nsecond"), (26, 6, "last")]);
+        assert!(cursor.is_at_end());
+        assert_eq!(Cursor::new("\n\n").paragraphs().next(), None);
+    }
+}
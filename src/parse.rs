@@ -0,0 +1,110 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{Cursor, Position, Span};
+
+/// Why [`Cursor::parse_next`] produced no value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseNextError<E> {
+    /// Only whitespace was left.
+    Eof { position: Position },
+    /// The word at `span` was rejected by [`FromStr`].
+    Invalid {
+        span: Span,
+        position: Position,
+        error: E,
+    },
+}
+
+impl<E> ParseNextError<E> {
+    /// Where the word starts, or the end of the input for `Eof`.
+    pub const fn position(&self) -> Position {
+        match self {
+            Self::Eof { position } | Self::Invalid { position, .. } => *position,
+        }
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for ParseNextError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Eof { position } => {
+                write!(f, "expected a value at {position}, found end of input")
+            }
+            Self::Invalid {
+                position, error, ..
+            } => write!(f, "invalid value at {position}: {error}"),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for ParseNextError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Eof { .. } => None,
+            Self::Invalid { error, .. } => Some(error),
+        }
+    }
+}
+
+impl Cursor<'_> {
+    /// Reads the next word, as [`Cursor::next_word`] finds it, and parses it
+    /// with [`FromStr`]. A word the value rejects is left unread. To parse
+    /// tokens other than whitespace-separated words, set a
+    /// [`WordSplitter`](crate::WordSplitter).
+    pub fn parse_next<T: FromStr>(&mut self) -> Result<T, ParseNextError<T::Err>> {
+        let checkpoint = self.checkpoint();
+        let Some((start, word)) = self.next_word() else {
+            return Err(ParseNextError::Eof {
+                position: Cursor::position(self),
+            });
+        };
+        word.parse().map_err(|error| {
+            self.restore(checkpoint);
+            self.skip_whitespace();
+            ParseNextError::Invalid {
+                span: Span::new(start, start + word.len()),
+                position: Cursor::position(self),
+                error,
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_next() {
+        let mut cursor = Cursor::new("3 -7\n2.5 yes");
+        let n: usize = cursor.parse_next().unwrap();
+        let m: i64 = cursor.parse_next().unwrap();
+        let x: f64 = cursor.parse_next().unwrap();
+        assert_eq!((n, m, x), (3, -7, 2.5));
+
+        let err = cursor.parse_next::<u8>().unwrap_err();
+        assert!(matches!(err, ParseNextError::Invalid { span, .. } if span == Span::new(9, 12)));
+        assert_eq!(err.position().line, 1);
+        assert_eq!(
+            err.to_string(),
+            "invalid value at 2:5: invalid digit found in string"
+        );
+        assert_eq!(cursor.parse_next::<String>().as_deref(), Ok("yes"));
+
+        let err = cursor.parse_next::<u8>().unwrap_err();
+        assert_eq!(
+            err,
+            ParseNextError::Eof {
+                position: cursor.position()
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_with_splitter() {
+        let mut cursor = Cursor::new("1,2;3").with_word_splitter(|c: char| c.is_ascii_digit());
+        let values: Vec<u32> = (0..3).map(|_| cursor.parse_next().unwrap()).collect();
+        assert_eq!(values, [1, 2, 3]);
+    }
+}
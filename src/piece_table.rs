@@ -0,0 +1,222 @@
+use std::borrow::Cow;
+use std::ops::Range;
+
+use crate::TextBuffer;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Source {
+    Original,
+    Added,
+}
+
+/// A run of text from one of the two buffers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Piece {
+    source: Source,
+    start: usize,
+    len: usize,
+}
+
+/// Text kept as the untouched original plus an append-only buffer of
+/// everything inserted since, stitched together by a list of pieces, as
+/// the storage of a [`CursorMut`](crate::CursorMut). An edit only splits
+/// pieces, so its cost doesn't depend on the size of the text, and the
+/// original can be borrowed rather than copied.
+#[derive(Debug, Clone)]
+pub struct PieceTable<'a> {
+    original: Cow<'a, str>,
+    added: String,
+    pieces: Vec<Piece>,
+    len: usize,
+    /// The whole text, when [`TextBuffer::make_contiguous`] has built it
+    /// since the last edit.
+    joined: Option<String>,
+}
+
+impl<'a> PieceTable<'a> {
+    pub fn new(original: impl Into<Cow<'a, str>>) -> Self {
+        let original = original.into();
+        let len = original.len();
+        let pieces = (len > 0)
+            .then_some(Piece {
+                source: Source::Original,
+                start: 0,
+                len,
+            })
+            .into_iter()
+            .collect();
+        Self {
+            original,
+            added: String::new(),
+            pieces,
+            len,
+            joined: None,
+        }
+    }
+
+    /// The text the table started with, which edits leave alone.
+    pub fn original(&self) -> &str {
+        &self.original
+    }
+
+    fn text_of(&self, piece: &Piece) -> &str {
+        let buffer = match piece.source {
+            Source::Original => &*self.original,
+            Source::Added => &self.added,
+        };
+        &buffer[piece.start..piece.start + piece.len]
+    }
+
+    /// The index of the piece containing `offset` and where in it `offset`
+    /// falls. The end of the text is at the end of the last piece.
+    fn locate(&self, offset: usize) -> (usize, usize) {
+        let mut start = 0;
+        for (i, piece) in self.pieces.iter().enumerate() {
+            if offset < start + piece.len {
+                return (i, offset - start);
+            }
+            start += piece.len;
+        }
+        (self.pieces.len(), 0)
+    }
+
+    /// Splits the pieces so one starts at `offset`, returning its index.
+    fn split_at(&mut self, offset: usize) -> usize {
+        let (i, within) = self.locate(offset);
+        if within == 0 {
+            return i;
+        }
+        let piece = self.pieces[i];
+        self.pieces[i].len = within;
+        let rest = Piece {
+            start: piece.start + within,
+            len: piece.len - within,
+            ..piece
+        };
+        self.pieces.insert(i + 1, rest);
+        i + 1
+    }
+}
+
+impl TextBuffer for PieceTable<'_> {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn is_char_boundary(&self, offset: usize) -> bool {
+        if offset > self.len {
+            return false;
+        }
+        let (i, within) = self.locate(offset);
+        within == 0 || self.text_of(&self.pieces[i]).is_char_boundary(within)
+    }
+
+    fn slice(&self, range: Range<usize>) -> Cow<'_, str> {
+        if let Some(joined) = &self.joined {
+            return Cow::Borrowed(&joined[range]);
+        }
+        let (first, within) = self.locate(range.start);
+        let mut slice = Cow::Borrowed("");
+        let (mut at, mut skip) = (range.start, within);
+        for piece in &self.pieces[first..] {
+            if at >= range.end {
+                break;
+            }
+            let text = &self.text_of(piece)[skip..];
+            let text = &text[..text.len().min(range.end - at)];
+            if slice.is_empty() {
+                slice = Cow::Borrowed(text);
+            } else {
+                slice.to_mut().push_str(text);
+            }
+            at += text.len();
+            skip = 0;
+        }
+        slice
+    }
+
+    fn replace_range(&mut self, range: Range<usize>, s: &str) {
+        assert!(
+            range.start <= range.end && self.is_char_boundary(range.start),
+            "invalid range start {}",
+            range.start
+        );
+        assert!(
+            self.is_char_boundary(range.end),
+            "invalid range end {}",
+            range.end
+        );
+        self.joined = None;
+        let start = self.split_at(range.start);
+        let end = self.split_at(range.end);
+        self.pieces.drain(start..end);
+        self.len = self.len - range.len() + s.len();
+        if s.is_empty() {
+            return;
+        }
+        // Typing extends the piece it is typed at the end of.
+        let added = self.added.len();
+        self.added.push_str(s);
+        if let Some(before) = start.checked_sub(1).map(|i| &mut self.pieces[i]) {
+            if before.source == Source::Added && before.start + before.len == added {
+                before.len += s.len();
+                return;
+            }
+        }
+        let piece = Piece {
+            source: Source::Added,
+            start: added,
+            len: s.len(),
+        };
+        self.pieces.insert(start, piece);
+    }
+
+    fn make_contiguous(&mut self) -> &str {
+        if self.joined.is_none() {
+            let joined = self
+                .pieces
+                .iter()
+                .map(|piece| self.text_of(piece))
+                .collect();
+            self.joined = Some(joined);
+        }
+        self.joined.as_deref().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CursorMut;
+
+    #[test]
+    fn test_piece_table() {
+        let original = String::from("hello world");
+        let mut table = PieceTable::new(original.as_str());
+        table.replace_range(5..5, ",");
+        table.replace_range(6..6, " dear");
+        assert_eq!(table.pieces.len(), 3);
+        table.replace_range(12..17, "th\u{e9}re");
+        assert_eq!(table.slice(0..5), "hello");
+        assert!(matches!(table.slice(1..4), Cow::Borrowed("ell")));
+        assert_eq!(table.slice(4..8), "o, d");
+        assert!(!table.is_char_boundary(15));
+        assert_eq!(table.make_contiguous(), "hello, dear th\u{e9}re");
+        table.replace_range(0..1, "");
+        assert_eq!(table.len(), 17);
+        assert_eq!(table.make_contiguous(), "ello, dear th\u{e9}re");
+        assert_eq!(table.original(), "hello world");
+    }
+
+    #[test]
+    fn test_cursor_over_piece_table() {
+        let mut cursor = CursorMut::new(PieceTable::new("a\nb"));
+        cursor.advance_by(3);
+        cursor.insert_str("c\nd");
+        cursor.retreat_by(4);
+        assert_eq!(cursor.delete_backward(1), "\n");
+        assert_eq!((cursor.line(), cursor.column()), (0, 1));
+        assert_eq!(cursor.cursor().remaining(), "bc\nd");
+        assert_eq!(cursor.into_inner().original(), "a\nb");
+    }
+}
@@ -0,0 +1,187 @@
+use std::fmt;
+
+use crate::{ColumnUnit, Cursor, LineTerminator};
+
+/// A full cursor position, from [`Cursor::position`]. All fields are
+/// zero-based; positions order by byte offset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Position {
+    pub byte: usize,
+    pub char_idx: usize,
+    pub line: usize,
+    /// The column, counted in the cursor's [`ColumnUnit`](crate::ColumnUnit).
+    pub column: usize,
+}
+
+/// Displays as a one-based `line:column`, the way errors report positions.
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line + 1, self.column + 1)
+    }
+}
+
+/// Converts a sorted sequence of byte offsets to `(line, column)` pairs in a
+/// single pass; see [`Cursor::line_cols_sorted_iter`].
+pub struct LineCols<'a, I> {
+    data: &'a str,
+    terminator: LineTerminator,
+    column_unit: ColumnUnit,
+    offsets: I,
+    at: usize,
+    line: usize,
+    column: usize,
+}
+
+impl<I: Iterator<Item = usize>> Iterator for LineCols<'_, I> {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut offset = self.offsets.next()?.min(self.data.len());
+        while !self.data.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        debug_assert!(offset >= self.at, "offsets must be sorted");
+        let offset = offset.max(self.at);
+
+        let lines = self.terminator.count(self.data, self.at, offset);
+        let since = if lines > 0 {
+            self.line += lines;
+            self.column = 0;
+            self.terminator.line_start(self.data, offset)
+        } else {
+            self.at
+        };
+        self.column += self.column_unit.width(&self.data[since..offset]);
+        self.at = offset;
+        Some((self.line, self.column))
+    }
+}
+
+impl<'a> Cursor<'a> {
+    pub const fn position(&self) -> Position {
+        Position {
+            byte: self.offset,
+            char_idx: self.char_idx,
+            line: self.line,
+            column: self.column,
+        }
+    }
+
+    /// Converts byte offsets, which must be sorted, to zero-based
+    /// `(line, column)` pairs with columns counted in the cursor's
+    /// [`ColumnUnit`], walking the input once. Offsets past the end are
+    /// clamped to it and offsets inside a char are rounded down to its start.
+    /// Unsorted input panics in debug builds.
+    pub fn line_cols_sorted(&self, offsets: &[usize]) -> Vec<(usize, usize)> {
+        self.line_cols_sorted_iter(offsets.iter().copied())
+            .collect()
+    }
+
+    /// The lazy form of [`Cursor::line_cols_sorted`].
+    pub fn line_cols_sorted_iter<I: IntoIterator<Item = usize>>(
+        &self,
+        offsets: I,
+    ) -> LineCols<'a, I::IntoIter> {
+        LineCols {
+            data: self.data,
+            terminator: self.terminator,
+            column_unit: self.column_unit,
+            offsets: offsets.into_iter(),
+            at: 0,
+            line: 0,
+            column: 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::XorShift;
+
+    fn naive(cursor: &Cursor, offset: usize) -> (usize, usize) {
+        let line = cursor.data[..offset].matches('\n').count();
+        (line, cursor.column_at(offset))
+    }
+
+    #[test]
+    fn test_position() {
+        let mut cursor = Cursor::new("añ\nb");
+        let start = cursor.position();
+        cursor.take_until("b");
+        let end = cursor.position();
+        assert_eq!(
+            end,
+            Position {
+                byte: 4,
+                char_idx: 3,
+                line: 1,
+                column: 0
+            }
+        );
+        assert!(start < end);
+        assert_eq!(end.to_string(), "2:1");
+
+        cursor.prev();
+        cursor.prev();
+        assert_eq!(cursor.position().char_idx, 1);
+        cursor.skip_whitespace_back();
+        cursor.prev_line();
+        assert_eq!(cursor.position(), start);
+    }
+
+    #[test]
+    fn test_line_cols() {
+        let cursor = Cursor::new("ab\ncé\n\nd");
+        let found = cursor.line_cols_sorted(&[0, 2, 3, 5, 5, 6, 7, 8, 100]);
+        assert_eq!(
+            found,
+            [
+                (0, 0),
+                (0, 2),
+                (1, 0),
+                (1, 1),
+                (1, 1),
+                (1, 2),
+                (2, 0),
+                (3, 0),
+                (3, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_inside_char_rounds_down() {
+        let cursor = Cursor::new("é!");
+        assert_eq!(cursor.line_cols_sorted(&[1, 2]), [(0, 0), (0, 1)]);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "sorted")]
+    fn test_unsorted_panics() {
+        Cursor::new("abc").line_cols_sorted(&[2, 1]);
+    }
+
+    #[test]
+    fn test_matches_per_offset_conversion() {
+        let mut rng = XorShift(0x5851_f42d_4c95_7f2d);
+        let text = rng.string(&['a', 'ü', '\n', ' ', '字', '\u{1f600}'], 5_000, 5_000);
+        let mut offsets: Vec<usize> = (0..2_000)
+            .map(|_| rng.below(text.len() + 1))
+            .filter(|&i| text.is_char_boundary(i))
+            .collect();
+        offsets.sort_unstable();
+
+        for unit in [ColumnUnit::Char, ColumnUnit::Byte, ColumnUnit::Utf16] {
+            let cursor = Cursor::new(&text).with_column_unit(unit);
+            let expected: Vec<_> = offsets.iter().map(|&i| naive(&cursor, i)).collect();
+            assert_eq!(cursor.line_cols_sorted(&offsets), expected);
+            let lazy: Vec<_> = cursor.line_cols_sorted_iter(offsets.clone()).collect();
+            assert_eq!(lazy, expected);
+            let single: Vec<_> = offsets.iter().map(|&i| cursor.position_of(i)).collect();
+            assert_eq!(single, expected);
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use std::borrow::Cow;
+use std::fmt;
+
+use crate::{Cursor, Position, Span};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuoteError {
+    /// The cursor was not at the opening quote.
+    NotAQuote { position: Position },
+    /// The input ended before the closing quote; `start` is the opening one.
+    Unterminated { start: Position },
+}
+
+impl fmt::Display for QuoteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotAQuote { position } => write!(f, "expected a quoted string at {position}"),
+            Self::Unterminated { start } => {
+                write!(f, "unterminated string starting at {start}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for QuoteError {}
+
+impl<'a> Cursor<'a> {
+    /// Consumes a string quoted with `quote`, where a backslash escapes the
+    /// char after it, so `\"` and `\\` don't end the string. Returns the
+    /// span of the whole literal and the raw text between the quotes, with
+    /// escapes left as written. The string may span lines. On error the
+    /// cursor does not move.
+    pub fn next_quoted(&mut self, quote: char) -> Result<(Span, &'a str), QuoteError> {
+        let start = Cursor::position(self);
+        let rest = self.forward();
+        let Some(body) = rest.strip_prefix(quote) else {
+            return Err(QuoteError::NotAQuote { position: start });
+        };
+        let mut escaped = false;
+        let close = body.char_indices().find(|&(_, c)| {
+            let closes = c == quote && !escaped;
+            escaped = c == '\\' && !escaped;
+            closes
+        });
+        let Some((len, _)) = close else {
+            return Err(QuoteError::Unterminated { start });
+        };
+        let open = quote.len_utf8();
+        let end = start.byte + open + len + open;
+        self.advance_to(end);
+        Ok((Span::new(start.byte, end), &body[..len]))
+    }
+}
+
+/// Iterator over shell-style words; see [`Cursor::words_quoted`].
+pub struct WordsQuoted<'a, 'b> {
+    cursor: &'b mut Cursor<'a>,
+}
+
+impl<'a> Cursor<'a> {
+    /// Splits the remaining input into words the way a POSIX shell does:
+    /// whitespace separates words unless it is quoted or escaped. Inside
+    /// `'...'` every char is literal; inside `"..."` a backslash escapes only
+    /// `"` and `\`; elsewhere it escapes any char. Quoted pieces join the
+    /// text around them, so `a"b c"` is the one word `ab c`.
+    ///
+    /// Each word comes with its span and its value after removing quotes and
+    /// escapes, which borrows the input when there were none. An unclosed
+    /// quote yields an error and ends the iteration.
+    pub fn words_quoted(&mut self) -> WordsQuoted<'a, '_> {
+        WordsQuoted { cursor: self }
+    }
+}
+
+impl<'a> Iterator for WordsQuoted<'a, '_> {
+    type Item = Result<(Span, Cow<'a, str>), QuoteError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cursor.skip_whitespace();
+        let start = Cursor::position(self.cursor);
+        let rest = self.cursor.forward();
+        if rest.is_empty() {
+            return None;
+        }
+        let (mut value, mut plain, mut quote) = (String::new(), true, None);
+        let mut end = rest.len();
+        let mut chars = rest.char_indices();
+        while let Some((i, c)) = chars.next() {
+            match (quote, c) {
+                (None, c) if c.is_whitespace() => {
+                    end = i;
+                    break;
+                }
+                (None, '\'') | (None, '"') => {
+                    quote = Some(c);
+                    plain = false;
+                }
+                (Some(q), c) if c == q => quote = None,
+                (None | Some('"'), '\\') => {
+                    plain = false;
+                    match chars.next() {
+                        Some((_, next)) => {
+                            if quote.is_some() && !matches!(next, '"' | '\\') {
+                                value.push('\\');
+                            }
+                            value.push(next);
+                        }
+                        None => value.push('\\'),
+                    }
+                }
+                (_, c) => value.push(c),
+            }
+        }
+        self.cursor.advance_to(start.byte + end);
+        if quote.is_some() {
+            return Some(Err(QuoteError::Unterminated { start }));
+        }
+        let span = Span::new(start.byte, self.cursor.offset);
+        let word = if plain {
+            Cow::Borrowed(&rest[..end])
+        } else {
+            Cow::Owned(value)
+        };
+        Some(Ok((span, word)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_quoted() {
+        let text = r#""say \"hi\"" 'it\'s' "a\\" "x"#;
+        let mut cursor = Cursor::new(text);
+        assert_eq!(
+            cursor.next_quoted('"'),
+            Ok((Span::new(0, 12), r#"say \"hi\""#))
+        );
+        cursor.next();
+        assert_eq!(cursor.next_quoted('\''), Ok((Span::new(13, 20), r"it\'s")));
+        cursor.next();
+        assert_eq!(cursor.next_quoted('"'), Ok((Span::new(21, 26), r"a\\")));
+        cursor.next();
+
+        let err = cursor.next_quoted('"').unwrap_err();
+        assert!(matches!(err, QuoteError::Unterminated { start } if start.byte == 27));
+        assert_eq!(err.to_string(), "unterminated string starting at 1:28");
+        assert_eq!(cursor.offset(), 27);
+        cursor.next();
+        assert!(matches!(
+            cursor.next_quoted('"'),
+            Err(QuoteError::NotAQuote { .. })
+        ));
+    }
+
+    fn shell_words(text: &str) -> Vec<String> {
+        let mut cursor = Cursor::new(text);
+        let words = cursor.words_quoted().map(|w| w.unwrap().1.into_owned());
+        words.collect()
+    }
+
+    #[test]
+    fn test_words_quoted() {
+        assert_eq!(
+            shell_words(r#"cp "two words" 'it''s' a\ b "q\"\n" x"y z"w "" "#),
+            ["cp", "two words", "its", "a b", "q\"\\n", "xy zw", ""]
+        );
+        assert_eq!(shell_words(r"'\n' \\"), [r"\n", r"\"]);
+    }
+
+    #[test]
+    fn test_words_quoted_spans_and_errors() {
+        let mut cursor = Cursor::new("plain 'ok' \"open");
+        let mut words = cursor.words_quoted();
+        let (span, word) = words.next().unwrap().unwrap();
+        assert_eq!((span, &*word), (Span::new(0, 5), "plain"));
+        assert!(matches!(word, Cow::Borrowed(_)));
+        let (span, word) = words.next().unwrap().unwrap();
+        assert_eq!((span, &*word), (Span::new(6, 10), "ok"));
+        let err = words.next().unwrap().unwrap_err();
+        assert!(matches!(err, QuoteError::Unterminated { start } if start.byte == 11));
+        assert!(words.next().is_none());
+    }
+
+    #[test]
+    fn test_multiline_quoted() {
+        let mut cursor = Cursor::new("«a\n»b");
+        assert_eq!(
+            cursor.next_quoted('«'),
+            Err(QuoteError::Unterminated {
+                start: Position::default()
+            })
+        );
+        let mut cursor = Cursor::new("|a\nb|c");
+        assert_eq!(cursor.next_quoted('|').map(|(_, s)| s), Ok("a\nb"));
+        assert_eq!((cursor.line(), cursor.column()), (1, 2));
+    }
+}
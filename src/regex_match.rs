@@ -0,0 +1,58 @@
+use regex_automata::meta::Regex;
+use regex_automata::{Anchored, Input};
+
+use crate::{Cursor, Span};
+
+impl<'a> Cursor<'a> {
+    /// Consumes a match of `regex` that starts at the cursor, returning its
+    /// span and text. The search is anchored at the cursor, so a failed
+    /// match never scans ahead for a later one. The regex sees the remaining
+    /// input only, so `^` matches at the cursor.
+    pub fn consume_regex(&mut self, regex: &Regex) -> Option<(Span, &'a str)> {
+        let rest = self.forward();
+        let found = regex.find(Input::new(rest).anchored(Anchored::Yes))?;
+        let start = self.offset;
+        self.advance_to(start + found.end());
+        Some((Span::new(start, self.offset), &rest[..found.end()]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_consume_regex() {
+        let number = Regex::new(r"^\d+(\.\d+)?").unwrap();
+        let space = Regex::new(r"\s+").unwrap();
+        let mut cursor = Cursor::new("12.5\n  x 7");
+        assert_eq!(
+            cursor.consume_regex(&number),
+            Some((Span::new(0, 4), "12.5"))
+        );
+        assert_eq!(cursor.consume_regex(&number), None);
+        assert_eq!(
+            cursor.consume_regex(&space),
+            Some((Span::new(4, 7), "\n  "))
+        );
+        assert_eq!((cursor.line(), cursor.column()), (1, 2));
+        // A match further on doesn't count.
+        assert_eq!(cursor.consume_regex(&number), None);
+        assert_eq!(cursor.consume_regex(&Regex::new(r"\d").unwrap()), None);
+        assert_eq!(cursor.offset(), 7);
+    }
+
+    #[test]
+    fn test_consume_regex_is_anchored() {
+        let text = format!("{}7", "x".repeat(10_000));
+        let digit = Regex::new(r"\d").unwrap();
+        let mut cursor = Cursor::new(&text);
+        assert_eq!(cursor.consume_regex(&digit), None);
+        assert_eq!(cursor.offset(), 0);
+        cursor.seek_to_offset(10_000).unwrap();
+        assert_eq!(
+            cursor.consume_regex(&digit),
+            Some((Span::new(10_000, 10_001), "7"))
+        );
+    }
+}
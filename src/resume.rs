@@ -0,0 +1,185 @@
+use std::fmt;
+
+use crate::{Cursor, LineTerminator};
+
+/// Bytes on each side of the offset covered by the fingerprint.
+const WINDOW: usize = 32;
+
+/// A saved cursor position that can be checked against the source before it
+/// is resumed, so a changed file is detected instead of silently seeking to
+/// the wrong place.
+///
+/// Text appended after the saved source is allowed; any other edit near the
+/// position, or one that changes the line count before it, is rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PositionToken {
+    offset: usize,
+    line: usize,
+    len: usize,
+    fingerprint: u64,
+    /// The cursor's terminator, which the line is counted with on resume.
+    terminator: LineTerminator,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResumeError {
+    /// The source around the position, or before it, is not what it was.
+    Changed,
+    /// The offset lies past the end of the source.
+    OutOfRange { offset: usize, len: usize },
+    /// The offset falls inside a multibyte char.
+    NotABoundary { offset: usize },
+}
+
+impl fmt::Display for ResumeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Changed => f.write_str("source changed since the position was saved"),
+            Self::OutOfRange { offset, len } => {
+                write!(
+                    f,
+                    "offset {offset} is past the end of the source ({len} bytes)"
+                )
+            }
+            Self::NotABoundary { offset } => write!(f, "offset {offset} is not a char boundary"),
+        }
+    }
+}
+
+impl std::error::Error for ResumeError {}
+
+impl PositionToken {
+    pub const fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub const fn line(&self) -> usize {
+        self.line
+    }
+}
+
+/// FNV-1a, which unlike `DefaultHasher` is stable across processes and Rust
+/// releases.
+fn fingerprint(data: &[u8], offset: usize) -> u64 {
+    let window = &data[offset.saturating_sub(WINDOW)..(offset + WINDOW).min(data.len())];
+    let mut hash = 0xcbf2_9ce4_8422_2325u64;
+    for &byte in offset.to_le_bytes().iter().chain(window) {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+impl<'a> Cursor<'a> {
+    /// Saves the current position as a [`PositionToken`].
+    pub fn token(&self) -> PositionToken {
+        PositionToken {
+            offset: self.offset,
+            line: self.line,
+            len: self.data.len(),
+            fingerprint: fingerprint(self.data.as_bytes(), self.offset),
+            terminator: self.terminator,
+        }
+    }
+
+    /// Creates a cursor over `data` positioned at `token`, after checking that
+    /// `data` still matches the source the token was taken from. The cursor
+    /// uses the line terminator of the one the token was saved from.
+    pub fn resume_at(data: &'a str, token: PositionToken) -> Result<Self, ResumeError> {
+        let offset = token.offset;
+        if offset > data.len() {
+            let len = data.len();
+            return Err(ResumeError::OutOfRange { offset, len });
+        }
+        if data.len() < token.len {
+            return Err(ResumeError::Changed);
+        }
+        if !data.is_char_boundary(offset) {
+            return Err(ResumeError::NotABoundary { offset });
+        }
+        if fingerprint(&data.as_bytes()[..token.len], offset) != token.fingerprint {
+            return Err(ResumeError::Changed);
+        }
+
+        let mut cursor = Cursor::new(data).with_line_terminator(token.terminator);
+        cursor.advance_to(offset);
+        if cursor.line != token.line {
+            return Err(ResumeError::Changed);
+        }
+        Ok(cursor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LOG: &str = "first entry\nsecond entry\nthird entry\n";
+
+    fn saved() -> PositionToken {
+        let mut cursor = Cursor::new(LOG);
+        cursor.take_until("second");
+        cursor.token()
+    }
+
+    #[test]
+    fn test_resume_identical() {
+        let mut cursor = Cursor::resume_at(LOG, saved()).unwrap();
+        assert_eq!(cursor.line(), 1);
+        assert_eq!(cursor.take_until("\n"), Some((12, "second entry")));
+    }
+
+    #[test]
+    fn test_resume_appended() {
+        let appended = format!("{LOG}fourth entry\n");
+        let cursor = Cursor::resume_at(&appended, saved()).unwrap();
+        assert_eq!(cursor.peek(), Some((12, 's')));
+        assert_eq!(cursor.line(), 1);
+    }
+
+    #[test]
+    fn test_resume_edited() {
+        let near = LOG.replace("second", "SECOND");
+        assert_eq!(
+            Cursor::resume_at(&near, saved()).err(),
+            Some(ResumeError::Changed)
+        );
+
+        let truncated = &LOG[..20];
+        assert_eq!(
+            Cursor::resume_at(truncated, saved()).err(),
+            Some(ResumeError::Changed)
+        );
+
+        let long = format!("{}\n{}", "x".repeat(100), "y".repeat(100));
+        let mut cursor = Cursor::new(&long);
+        cursor.take_until("y");
+        let token = cursor.token();
+        let moved_line = long.replacen('x', "\n", 1);
+        let err = Cursor::resume_at(&moved_line, token).err();
+        assert_eq!(err, Some(ResumeError::Changed));
+    }
+
+    #[test]
+    fn test_resume_keeps_line_terminator() {
+        let text = "a\rb\rc";
+        let mut cursor = Cursor::new(text).with_line_terminator(LineTerminator::Ascii);
+        cursor.take_until("c");
+        let resumed = Cursor::resume_at(text, cursor.token()).unwrap();
+        assert_eq!((resumed.line(), resumed.column()), (2, 0));
+        assert_eq!(resumed.line_terminator(), LineTerminator::Ascii);
+    }
+
+    #[test]
+    fn test_resume_out_of_range_and_boundary() {
+        let err = Cursor::resume_at("short", saved()).err();
+        assert_eq!(err, Some(ResumeError::OutOfRange { offset: 12, len: 5 }));
+
+        let mut cursor = Cursor::new("aaaa");
+        cursor.next();
+        cursor.next();
+        let err = Cursor::resume_at("aéa", cursor.token()).err();
+        assert_eq!(err, Some(ResumeError::NotABoundary { offset: 2 }));
+    }
+}
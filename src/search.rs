@@ -0,0 +1,862 @@
+use std::ops::Range;
+
+use memchr::memmem::{self, Finder};
+
+use crate::{Cursor, Position, Span};
+
+/// Something that can be matched at a position in the input: a `char`, a
+/// `&str`, a set of chars, or a `char` predicate.
+pub trait Needle {
+    /// Byte length of the match at the start of `haystack`, if any.
+    fn prefix_len(&mut self, haystack: &str) -> Option<usize>;
+
+    /// Byte length of the match at the end of `haystack`, if any.
+    fn suffix_len(&mut self, haystack: &str) -> Option<usize>;
+
+    /// Byte range of the first match in `haystack`, if any. The default
+    /// tries [`Needle::prefix_len`] at every char boundary.
+    fn find_in(&mut self, haystack: &str) -> Option<Range<usize>> {
+        let boundaries = haystack.char_indices().map(|(i, _)| i);
+        boundaries
+            .chain([haystack.len()])
+            .find_map(|i| self.prefix_len(&haystack[i..]).map(|len| i..i + len))
+    }
+
+    /// [`Needle::prefix_len`] for cursors set up with
+    /// [`Cursor::with_nfc_matching`]. Only `&str` needles normalize.
+    #[cfg(feature = "unicode-normalization")]
+    fn prefix_len_nfc(&mut self, haystack: &str) -> Option<usize> {
+        self.prefix_len(haystack)
+    }
+
+    /// Byte range of the last match in `haystack`, if any. The default
+    /// tries [`Needle::suffix_len`] at every char boundary from the end.
+    fn rfind_in(&mut self, haystack: &str) -> Option<Range<usize>> {
+        let boundaries = haystack.char_indices().rev().map(|(i, _)| i);
+        [haystack.len()]
+            .into_iter()
+            .chain(boundaries)
+            .find_map(|i| self.suffix_len(&haystack[..i]).map(|len| i - len..i))
+    }
+}
+
+/// The range of the char that `haystack.find` found at `start`.
+fn char_at(haystack: &str, start: Option<usize>) -> Option<Range<usize>> {
+    let start = start?;
+    let len = haystack[start..].chars().next()?.len_utf8();
+    Some(start..start + len)
+}
+
+impl Needle for char {
+    fn prefix_len(&mut self, haystack: &str) -> Option<usize> {
+        haystack.starts_with(*self).then(|| self.len_utf8())
+    }
+
+    fn suffix_len(&mut self, haystack: &str) -> Option<usize> {
+        haystack.ends_with(*self).then(|| self.len_utf8())
+    }
+
+    fn find_in(&mut self, haystack: &str) -> Option<Range<usize>> {
+        let start = haystack.find(*self)?;
+        Some(start..start + self.len_utf8())
+    }
+
+    fn rfind_in(&mut self, haystack: &str) -> Option<Range<usize>> {
+        let start = haystack.rfind(*self)?;
+        Some(start..start + self.len_utf8())
+    }
+}
+
+impl Needle for &str {
+    fn prefix_len(&mut self, haystack: &str) -> Option<usize> {
+        haystack.starts_with(*self).then_some(self.len())
+    }
+
+    fn suffix_len(&mut self, haystack: &str) -> Option<usize> {
+        haystack.ends_with(*self).then_some(self.len())
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    fn prefix_len_nfc(&mut self, haystack: &str) -> Option<usize> {
+        crate::normalize::prefix_len_nfc(haystack, self)
+    }
+
+    fn find_in(&mut self, haystack: &str) -> Option<Range<usize>> {
+        let start = memmem::find(haystack.as_bytes(), self.as_bytes())?;
+        Some(start..start + self.len())
+    }
+
+    fn rfind_in(&mut self, haystack: &str) -> Option<Range<usize>> {
+        let start = memmem::rfind(haystack.as_bytes(), self.as_bytes())?;
+        Some(start..start + self.len())
+    }
+}
+
+impl Needle for &[char] {
+    fn prefix_len(&mut self, haystack: &str) -> Option<usize> {
+        let c = haystack.chars().next()?;
+        self.contains(&c).then(|| c.len_utf8())
+    }
+
+    fn suffix_len(&mut self, haystack: &str) -> Option<usize> {
+        let c = haystack.chars().next_back()?;
+        self.contains(&c).then(|| c.len_utf8())
+    }
+
+    fn find_in(&mut self, haystack: &str) -> Option<Range<usize>> {
+        char_at(haystack, haystack.find(*self))
+    }
+
+    fn rfind_in(&mut self, haystack: &str) -> Option<Range<usize>> {
+        char_at(haystack, haystack.rfind(*self))
+    }
+}
+
+impl<const N: usize> Needle for [char; N] {
+    fn prefix_len(&mut self, haystack: &str) -> Option<usize> {
+        self.as_slice().prefix_len(haystack)
+    }
+
+    fn suffix_len(&mut self, haystack: &str) -> Option<usize> {
+        self.as_slice().suffix_len(haystack)
+    }
+
+    fn find_in(&mut self, haystack: &str) -> Option<Range<usize>> {
+        self.as_slice().find_in(haystack)
+    }
+
+    fn rfind_in(&mut self, haystack: &str) -> Option<Range<usize>> {
+        self.as_slice().rfind_in(haystack)
+    }
+}
+
+impl<F: FnMut(char) -> bool> Needle for F {
+    fn prefix_len(&mut self, haystack: &str) -> Option<usize> {
+        let c = haystack.chars().next()?;
+        self(c).then(|| c.len_utf8())
+    }
+
+    fn suffix_len(&mut self, haystack: &str) -> Option<usize> {
+        let c = haystack.chars().next_back()?;
+        self(c).then(|| c.len_utf8())
+    }
+
+    fn find_in(&mut self, haystack: &str) -> Option<Range<usize>> {
+        char_at(haystack, haystack.find(&mut *self))
+    }
+
+    fn rfind_in(&mut self, haystack: &str) -> Option<Range<usize>> {
+        char_at(haystack, haystack.rfind(&mut *self))
+    }
+}
+
+/// A `&str` needle with its `memmem` searcher built once, for iterators that
+/// search for it over and over.
+struct StrFinder<'b>(Finder<'b>);
+
+impl Needle for StrFinder<'_> {
+    fn prefix_len(&mut self, haystack: &str) -> Option<usize> {
+        let needle = self.0.needle();
+        haystack
+            .as_bytes()
+            .starts_with(needle)
+            .then_some(needle.len())
+    }
+
+    fn suffix_len(&mut self, haystack: &str) -> Option<usize> {
+        let needle = self.0.needle();
+        haystack
+            .as_bytes()
+            .ends_with(needle)
+            .then_some(needle.len())
+    }
+
+    fn find_in(&mut self, haystack: &str) -> Option<Range<usize>> {
+        let start = self.0.find(haystack.as_bytes())?;
+        Some(start..start + self.0.needle().len())
+    }
+
+    fn rfind_in(&mut self, haystack: &str) -> Option<Range<usize>> {
+        let needle = self.0.needle();
+        let start = memmem::rfind(haystack.as_bytes(), needle)?;
+        Some(start..start + needle.len())
+    }
+}
+
+// Searching is done on bytes: a valid UTF-8 needle can only match a valid
+// UTF-8 haystack at char boundaries, so every hit is a valid cursor position.
+
+impl<'a> Cursor<'a> {
+    /// Returns whether `needle` matches at the cursor. Never moves the cursor;
+    /// at the end of the input only an empty `&str` matches.
+    pub fn followed_by(&self, mut needle: impl Needle) -> bool {
+        self.match_len(&mut needle).is_some()
+    }
+
+    /// Byte length of the match of `needle` at the cursor, if any.
+    pub(crate) fn match_len(&self, needle: &mut impl Needle) -> Option<usize> {
+        #[cfg(feature = "unicode-normalization")]
+        if self.nfc {
+            return needle.prefix_len_nfc(self.forward());
+        }
+        needle.prefix_len(self.forward())
+    }
+
+    /// Like [`str::starts_with`] on the remaining input: the same check as
+    /// [`Cursor::followed_by`], which it calls.
+    pub fn starts_with(&self, needle: impl Needle) -> bool {
+        self.followed_by(needle)
+    }
+
+    pub fn not_followed_by(&self, needle: impl Needle) -> bool {
+        !self.followed_by(needle)
+    }
+
+    /// Returns whether `needle` matches immediately before the cursor. At the
+    /// start of the input only an empty `&str` matches.
+    pub fn preceded_by(&self, mut needle: impl Needle) -> bool {
+        needle.suffix_len(self.backward()).is_some()
+    }
+
+    /// Returns the byte offset of the next match of `needle` at or after the
+    /// cursor, without moving it. Like [`str::find`], but for any [`Needle`].
+    pub fn find_next(&self, mut needle: impl Needle) -> Option<usize> {
+        let found = needle.find_in(self.forward())?;
+        Some(self.offset + found.start)
+    }
+
+    /// Returns the byte offset of the last match of `needle` that lies before
+    /// the cursor, searching back through the consumed input, without moving.
+    pub fn rfind(&self, mut needle: impl Needle) -> Option<usize> {
+        needle.rfind_in(self.backward()).map(|found| found.start)
+    }
+
+    /// Moves the cursor back to the start of the last match of `needle`
+    /// before it and returns its offset, for finding the start of the
+    /// enclosing line, block or tag. Leaves the cursor untouched if there is
+    /// none.
+    pub fn seek_back_to(&mut self, needle: impl Needle) -> Option<usize> {
+        let at = self.rfind(needle)?;
+        self.retreat_to(at);
+        Some(at)
+    }
+
+    /// Consumes consecutive matches of `needle`, like
+    /// [`str::trim_start_matches`], returning the start offset and the
+    /// consumed slice. An empty match ends the run.
+    pub fn consume_matching(&mut self, mut needle: impl Needle) -> (usize, &'a str) {
+        let start = self.offset;
+        let mut end = start;
+        while let Some(len) = needle.prefix_len(&self.data[end..]).filter(|&len| len > 0) {
+            end += len;
+        }
+        self.advance_to(end);
+        (start, &self.data[start..end])
+    }
+
+    /// Moves the cursor to the start of the next match of `needle` and
+    /// returns its offset. Leaves the cursor untouched if there is none.
+    pub fn skip_to(&mut self, needle: impl Needle) -> Option<usize> {
+        let at = self.find_next(needle)?;
+        self.advance_to(at);
+        Some(at)
+    }
+
+    /// Returns the byte offset of the next occurrence of `needle` at or after
+    /// the cursor, without moving it.
+    pub fn find_str(&self, needle: &str) -> Option<usize> {
+        self.find_with(&Finder::new(needle))
+    }
+
+    fn find_with(&self, finder: &Finder<'_>) -> Option<usize> {
+        finder
+            .find(self.forward().as_bytes())
+            .map(|pos| self.offset + pos)
+    }
+
+    /// Consumes everything up to (but not including) the next occurrence of
+    /// `needle`. Leaves the cursor untouched if `needle` does not occur.
+    pub fn take_until(&mut self, needle: &str) -> Option<(usize, &'a str)> {
+        let start = self.offset;
+        let end = self.find_str(needle)?;
+        self.advance_to(end);
+        Some((start, &self.data[start..end]))
+    }
+
+    /// Jumps to the next occurrence of `needle` at or after the cursor and
+    /// returns its span, leaving the cursor at its start. The search runs on
+    /// bytes with `memmem`, and the line count is brought up to date in bulk
+    /// over the skipped region, so this stays fast on very large inputs.
+    /// Leaves the cursor untouched if `needle` does not occur.
+    pub fn seek_to_str(&mut self, needle: &str) -> Option<Span> {
+        let start = self.find_str(needle)?;
+        self.advance_to(start);
+        Some(Span::new(start, start + needle.len()))
+    }
+
+    /// Consumes chars while `pred` holds, returning the start offset and the
+    /// consumed slice, which is empty if the first char fails.
+    pub fn consume_while(&mut self, mut pred: impl FnMut(char) -> bool) -> (usize, &'a str) {
+        let start = self.offset;
+        let len = self.forward().find(|c| !pred(c));
+        self.advance_to(len.map_or(self.data.len(), |len| start + len));
+        (start, &self.data[start..self.offset])
+    }
+
+    /// Consumes chars up to, but not including, the first one satisfying
+    /// `pred`, or to the end of the input if none does.
+    pub fn consume_until(&mut self, mut pred: impl FnMut(char) -> bool) -> (usize, &'a str) {
+        self.consume_while(|c| !pred(c))
+    }
+
+    /// Skips past the first char satisfying `pred` and returns it, or skips
+    /// to the end of the input and returns `None` if none does.
+    pub fn skip_until(&mut self, pred: impl FnMut(char) -> bool) -> Option<char> {
+        self.consume_until(pred);
+        self.next_char()
+    }
+
+    /// Consumes the longest of `literals` that matches at the cursor, such as
+    /// `<<=` rather than `<` or `<<` from an operator table, returning its
+    /// start offset and text.
+    pub fn consume_longest(&mut self, literals: &[&str]) -> Option<(usize, &'a str)> {
+        let rest = self.forward();
+        let len = literals
+            .iter()
+            .filter(|literal| rest.starts_with(*literal))
+            .map(|literal| literal.len())
+            .max()?;
+        let start = self.offset;
+        self.advance_to(start + len);
+        Some((start, &rest[..len]))
+    }
+
+    /// Splits the remaining input on `sep`, consuming it as the iterator
+    /// advances. Mirrors [`str::split`]; an empty separator yields the whole
+    /// remainder as a single piece.
+    pub fn split_str<'b>(&'b mut self, sep: &'b str) -> SplitStr<'a, 'b> {
+        SplitStr {
+            cursor: self,
+            finder: Finder::new(sep),
+            finished: false,
+        }
+    }
+
+    /// Iterates over the non-overlapping occurrences of `needle` in the
+    /// remaining input, moving the cursor past each one: [`Cursor::matches`]
+    /// for a `&str` that builds its searcher once. An empty needle yields
+    /// nothing.
+    pub fn find_all<'b>(&'b mut self, needle: &'b str) -> FindAll<'a, 'b> {
+        FindAll {
+            matches: Matches {
+                cursor: self,
+                needle: StrFinder(Finder::new(needle)),
+                start: None,
+            },
+        }
+    }
+
+    /// Iterates over the non-overlapping matches of `needle` in the remaining
+    /// input, like [`str::match_indices`], moving the cursor past each one.
+    /// Empty matches end the iteration. [`Matches::match_start`] has the line
+    /// and column of the latest hit. To search for a `&str` many times, use
+    /// [`Cursor::find_all`].
+    pub fn matches<N: Needle>(&mut self, needle: N) -> Matches<'a, '_, N> {
+        Matches {
+            cursor: self,
+            needle,
+            start: None,
+        }
+    }
+
+    /// Binary-searches the remaining lines for the first one on which `pred`
+    /// is false, returning its line number and byte offset, or the position
+    /// at the end of the input if there is none. The search runs over the
+    /// line index if the cursor has one. `pred` sees each line without its
+    /// terminator and must be monotone: true for a prefix of the lines and
+    /// false for the rest, as `key < x` is on lines sorted by key.
+    pub fn partition_point_lines(&self, mut pred: impl FnMut(&str) -> bool) -> (usize, usize) {
+        let terminator = self.terminator;
+        if let Some(index) = &self.line_index {
+            let end = self.data.len();
+            let last = index.line_of(end);
+            // The lines that start before the end; the input may be a prefix
+            // of the indexed text.
+            let lines = last + usize::from(index.line_start(last).is_some_and(|s| s < end));
+            let extent = |line: usize| {
+                let start = index.line_start(line).map_or(end, |s| s.max(self.offset));
+                let next = index.line_start(line + 1).map_or(end, |s| s.min(end));
+                (start, next)
+            };
+            let (mut lo, mut hi) = (self.line, lines);
+            while lo < hi {
+                let mid = lo + (hi - lo) / 2;
+                let (start, next) = extent(mid);
+                if pred(terminator.strip(&self.data[start..next])) {
+                    lo = mid + 1;
+                } else {
+                    hi = mid;
+                }
+            }
+            return if lo < lines {
+                (lo, extent(lo).0)
+            } else {
+                (last, end)
+            };
+        }
+        let (mut lo, mut hi) = (self.offset, self.data.len());
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            let start = terminator.line_start(self.data, mid).max(lo);
+            let end = terminator
+                .nth_after(self.data, start, 0)
+                .unwrap_or(self.data.len());
+            if pred(terminator.strip(&self.data[start..end])) {
+                lo = end;
+            } else {
+                hi = start;
+            }
+        }
+        let skipped = terminator.count(self.data, self.offset, lo);
+        (self.line + skipped, lo)
+    }
+
+    /// Moves the cursor to the line found by [`Cursor::partition_point_lines`].
+    pub fn seek_to_partition_point(&mut self, pred: impl FnMut(&str) -> bool) {
+        let (_, offset) = self.partition_point_lines(pred);
+        self.advance_to(offset);
+    }
+}
+
+pub struct SplitStr<'a, 'b> {
+    cursor: &'b mut Cursor<'a>,
+    finder: Finder<'b>,
+    finished: bool,
+}
+
+impl<'a> Iterator for SplitStr<'a, '_> {
+    type Item = (usize, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        let start = self.cursor.offset;
+        let sep_len = self.finder.needle().len();
+        let end = match self.cursor.find_with(&self.finder) {
+            Some(end) if sep_len > 0 => end,
+            _ => {
+                self.finished = true;
+                self.cursor.data.len()
+            }
+        };
+        let piece = &self.cursor.data[start..end];
+        let next = if self.finished { end } else { end + sep_len };
+        self.cursor.advance_to(next);
+        Some((start, piece))
+    }
+}
+
+pub struct FindAll<'a, 'b> {
+    matches: Matches<'a, 'b, StrFinder<'b>>,
+}
+
+impl<'a> Iterator for FindAll<'a, '_> {
+    type Item = (usize, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (span, text) = self.matches.next()?;
+        Some((span.start, text))
+    }
+}
+
+/// Iterator over the matches of a [`Needle`]; see [`Cursor::matches`].
+pub struct Matches<'a, 'b, N> {
+    cursor: &'b mut Cursor<'a>,
+    needle: N,
+    start: Option<Position>,
+}
+
+impl<N> Matches<'_, '_, N> {
+    /// Where the match yielded last starts.
+    pub const fn match_start(&self) -> Option<Position> {
+        self.start
+    }
+}
+
+impl<'a, N: Needle> Iterator for Matches<'a, '_, N> {
+    type Item = (Span, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let base = self.cursor.offset;
+        let found = self.needle.find_in(self.cursor.forward())?;
+        if found.is_empty() {
+            return None;
+        }
+        let span = Span::new(base + found.start, base + found.end);
+        self.cursor.advance_to(span.start);
+        self.start = Some(Cursor::position(self.cursor));
+        self.cursor.advance_to(span.end);
+        Some((span, &self.cursor.data[span.start..span.end]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::XorShift;
+
+    /// Char-window scan used as the reference implementation.
+    fn naive_find(haystack: &str, from: usize, needle: &str) -> Option<usize> {
+        haystack[from..]
+            .char_indices()
+            .map(|(i, _)| from + i)
+            .find(|&i| haystack[i..].starts_with(needle))
+    }
+
+    fn naive_line(haystack: &str, offset: usize) -> usize {
+        haystack[..offset].matches('\n').count()
+    }
+
+    #[test]
+    fn test_lookaround_does_not_move() {
+        let mut cursor = Cursor::new("a/*b");
+        cursor.next();
+        assert!(cursor.followed_by('/'));
+        assert!(cursor.followed_by("/*"));
+        assert!(cursor.not_followed_by("//"));
+        assert!(cursor.followed_by(['/', '*']));
+        assert!(cursor.followed_by(|c: char| c.is_ascii_punctuation()));
+        assert!(cursor.preceded_by('a'));
+        assert!(cursor.preceded_by(char::is_alphabetic));
+        assert_eq!(cursor.peek(), Some((1, '/')));
+    }
+
+    #[test]
+    fn test_lookaround_at_boundaries() {
+        let mut cursor = Cursor::new("-x");
+        assert!(cursor.is_at_start());
+        assert!(!cursor.preceded_by(char::is_whitespace));
+        assert!(!cursor.preceded_by(&['-'][..]));
+        assert!(cursor.preceded_by(""));
+
+        cursor.next();
+        cursor.next();
+        assert!(cursor.is_at_end());
+        assert!(!cursor.followed_by('x'));
+        assert!(!cursor.followed_by("x"));
+        assert!(!cursor.followed_by(|_| true));
+        assert!(cursor.not_followed_by(['/', '*']));
+    }
+
+    #[test]
+    fn test_starts_with() {
+        let mut cursor = Cursor::new("x => é");
+        cursor.next();
+        assert!(cursor.starts_with(' '));
+        assert!(cursor.starts_with(" =>"));
+        assert!(!cursor.starts_with("=>"));
+        assert!(cursor.starts_with([' ', '\t']));
+        assert!(cursor.starts_with(char::is_whitespace));
+        cursor.take_until("é");
+        assert!(cursor.starts_with(|c: char| !c.is_ascii()));
+        assert_eq!(cursor.offset(), 5);
+    }
+
+    #[test]
+    fn test_preceded_by_multibyte() {
+        let mut cursor = Cursor::new("日本語!");
+        cursor.next();
+        cursor.next();
+        cursor.next();
+        assert!(cursor.preceded_by("本語"));
+        assert!(cursor.preceded_by('語'));
+        assert!(!cursor.preceded_by("日語"));
+        assert!(cursor.followed_by('!'));
+    }
+
+    #[test]
+    fn test_find_str() {
+        let mut cursor = Cursor::new("foo bar foo");
+        assert_eq!(cursor.find_str("foo"), Some(0));
+        cursor.next();
+        assert_eq!(cursor.find_str("foo"), Some(8));
+        assert_eq!(cursor.find_str("baz"), None);
+        assert_eq!(cursor.peek_char(), Some('o'));
+    }
+
+    #[test]
+    fn test_take_until_updates_line() {
+        let mut cursor = Cursor::new("a\nb\nc -> d");
+        assert_eq!(cursor.take_until("->"), Some((0, "a\nb\nc ")));
+        assert_eq!(cursor.line(), 2);
+        assert_eq!(cursor.peek(), Some((6, '-')));
+        assert_eq!(cursor.take_until("missing"), None);
+        assert_eq!(cursor.peek(), Some((6, '-')));
+    }
+
+    #[test]
+    fn test_seek_to_str() {
+        let log = "ok\n".repeat(10_000) + "é ERROR disk\nok\nERROR again";
+        let mut cursor = Cursor::new(&log);
+        let span = cursor.seek_to_str("ERROR").unwrap();
+        assert_eq!(span, Span::new(30_003, 30_008));
+        assert_eq!((cursor.line(), cursor.column()), (10_000, 2));
+        assert_eq!(cursor.position().char_idx, 30_002);
+        assert_eq!(cursor.seek_to_str("ERROR"), Some(span));
+        cursor.advance_by(1);
+        assert_eq!(cursor.seek_to_str("ERROR").map(|s| s.start), Some(30_017));
+        assert_eq!(cursor.line(), 10_002);
+        assert_eq!(cursor.seek_to_str("missing"), None);
+        assert_eq!(cursor.offset(), 30_017);
+    }
+
+    #[test]
+    fn test_consume_while() {
+        let mut cursor = Cursor::new("123é4 rest");
+        assert_eq!(cursor.consume_while(|c| c.is_ascii_digit()), (0, "123"));
+        assert_eq!(cursor.consume_while(|c| c.is_ascii_digit()), (3, ""));
+        assert_eq!(cursor.consume_while(|c| !c.is_whitespace()), (3, "é4"));
+        cursor.next();
+        assert_eq!(cursor.consume_while(|_| true), (7, "rest"));
+        assert!(cursor.is_at_end());
+        assert_eq!(cursor.column(), 10);
+    }
+
+    #[test]
+    fn test_consume_until_and_skip_until() {
+        let mut cursor = Cursor::new("\"a;b\";\nc");
+        cursor.next();
+        assert_eq!(cursor.consume_until(|c| c == '"'), (1, "a;b"));
+        assert_eq!(cursor.peek_char(), Some('"'));
+        assert_eq!(cursor.skip_until(|c| c == ';'), Some(';'));
+        assert_eq!(cursor.offset(), 6);
+        assert_eq!(cursor.consume_until(|c| c == ';'), (6, "\nc"));
+        assert_eq!((cursor.line(), cursor.column()), (1, 1));
+
+        let mut cursor = Cursor::new("abc");
+        assert_eq!(cursor.skip_until(|c| c == ';'), None);
+        assert!(cursor.is_at_end());
+    }
+
+    #[test]
+    fn test_generic_find() {
+        let cursor = Cursor::new("ab, cé; d");
+        assert_eq!(cursor.find_next(';'), Some(7));
+        assert_eq!(cursor.find_next("é;"), Some(5));
+        assert_eq!(cursor.find_next([';', ',']), Some(2));
+        assert_eq!(cursor.find_next(&[' '][..]), Some(3));
+        assert_eq!(cursor.find_next(|c: char| !c.is_ascii()), Some(5));
+        assert_eq!(cursor.find_next(""), Some(0));
+        assert_eq!(cursor.find_next('x'), None);
+
+        let mut cursor = Cursor::new("\n\n x");
+        cursor.next();
+        let by_ref = &mut cursor;
+        assert_eq!(by_ref.find_next(""), Some(1));
+        assert_eq!(by_ref.find_next('x'), Some(3));
+        assert_eq!(cursor.offset(), 1);
+    }
+
+    #[test]
+    fn test_consume_matching_and_skip_to() {
+        let mut cursor = Cursor::new("ababa--\n\n x");
+        assert_eq!(cursor.consume_matching("ab"), (0, "abab"));
+        assert_eq!(cursor.consume_matching(['-', 'a']), (4, "a--"));
+        assert_eq!(cursor.consume_matching(""), (7, ""));
+        assert_eq!(cursor.consume_matching(char::is_whitespace), (7, "\n\n "));
+        assert_eq!((cursor.line(), cursor.column()), (2, 1));
+
+        let mut cursor = Cursor::new("key:\n value");
+        assert_eq!(cursor.skip_to(char::is_alphabetic), Some(0));
+        assert_eq!(cursor.skip_to('v'), Some(6));
+        assert_eq!((cursor.line(), cursor.column()), (1, 1));
+        assert_eq!(cursor.skip_to(':'), None);
+        assert_eq!(cursor.offset(), 6);
+    }
+
+    #[test]
+    fn test_rfind_and_seek_back_to() {
+        let text = "<a>\n<b>é</b>\n</a>";
+        let mut cursor = Cursor::new(text);
+        cursor.seek_to_str("é").unwrap();
+        assert_eq!(cursor.rfind('<'), Some(4));
+        assert_eq!(cursor.rfind("<a"), Some(0));
+        assert_eq!(cursor.rfind(['\n', '>']), Some(6));
+        assert_eq!(cursor.rfind(char::is_whitespace), Some(3));
+        assert_eq!(cursor.rfind("é"), None);
+        assert_eq!(cursor.rfind(""), Some(7));
+
+        assert_eq!(cursor.seek_back_to("<"), Some(4));
+        assert_eq!((cursor.line(), cursor.column()), (1, 0));
+        assert_eq!(cursor.seek_back_to("<b"), None);
+        assert_eq!(cursor.seek_back_to("<a>"), Some(0));
+        assert!(cursor.is_at_start());
+    }
+
+    #[test]
+    fn test_default_find_in() {
+        struct Twice(char);
+        impl Needle for Twice {
+            fn prefix_len(&mut self, haystack: &str) -> Option<usize> {
+                let mut chars = haystack.chars();
+                (chars.next()? == self.0 && chars.next()? == self.0).then(|| 2 * self.0.len_utf8())
+            }
+
+            fn suffix_len(&mut self, haystack: &str) -> Option<usize> {
+                let mut chars = haystack.chars().rev();
+                let twice = chars.next()? == self.0 && chars.next()? == self.0;
+                twice.then(|| 2 * self.0.len_utf8())
+            }
+        }
+        assert_eq!(Twice('é').find_in("aébééc"), Some(4..8));
+        assert_eq!(Twice('x').find_in("axbx"), None);
+        assert_eq!(Twice('é').rfind_in("ééaéé"), Some(5..9));
+        assert_eq!(Twice('x').rfind_in("x"), None);
+    }
+
+    #[test]
+    fn test_consume_longest() {
+        const OPS: &[&str] = &["<", "<=", "<<", "<<=", "="];
+        let mut cursor = Cursor::new("<<=<=<x");
+        assert_eq!(cursor.consume_longest(OPS), Some((0, "<<=")));
+        assert_eq!(cursor.consume_longest(OPS), Some((3, "<=")));
+        assert_eq!(cursor.consume_longest(OPS), Some((5, "<")));
+        assert_eq!(cursor.consume_longest(OPS), None);
+        assert_eq!(cursor.offset(), 6);
+        assert_eq!(cursor.consume_longest(&[]), None);
+    }
+
+    #[test]
+    fn test_split_str() {
+        let mut cursor = Cursor::new("a, b,\nc, ");
+        let pieces: Vec<_> = cursor.split_str(", ").collect();
+        assert_eq!(pieces, vec![(0, "a"), (3, "b,\nc"), (9, "")]);
+        assert_eq!(cursor.line(), 1);
+        assert_eq!(cursor.next(), None);
+    }
+
+    #[test]
+    fn test_find_all() {
+        let mut cursor = Cursor::new("aaa\naa");
+        let hits: Vec<_> = cursor.find_all("aa").collect();
+        assert_eq!(hits, vec![(0, "aa"), (4, "aa")]);
+        assert_eq!(cursor.line(), 1);
+        assert_eq!(Cursor::new("abc").find_all("").next(), None);
+    }
+
+    #[test]
+    fn test_matches() {
+        let mut cursor = Cursor::new("let x = 1;\nlet yé = 22;");
+        let mut hits = cursor.matches(|c: char| c.is_ascii_digit());
+        assert_eq!(hits.match_start(), None);
+        let mut found = Vec::new();
+        while let Some((span, text)) = hits.next() {
+            found.push((span, text, hits.match_start().unwrap().to_string()));
+        }
+        assert_eq!(
+            found,
+            [
+                (Span::new(8, 9), "1", "1:9".to_string()),
+                (Span::new(21, 22), "2", "2:10".to_string()),
+                (Span::new(22, 23), "2", "2:11".to_string()),
+            ]
+        );
+        assert_eq!(cursor.offset(), 23);
+
+        let mut cursor = Cursor::new("ab ab\nab");
+        let words: Vec<_> = cursor.matches("ab").map(|(span, _)| span.start).collect();
+        assert_eq!(words, [0, 3, 6]);
+        assert_eq!(Cursor::new("abc").matches("").next(), None);
+    }
+
+    #[test]
+    fn test_search_matches_naive_on_random_unicode() {
+        let alphabet = ['a', 'b', '\n', 'é', '👋', 'ß', ' '];
+        let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+        for _ in 0..500 {
+            let haystack = rng.string(&alphabet, 0, 64);
+            let needle = rng.string(&alphabet, 1, 3);
+
+            let mut cursor = Cursor::new(&haystack);
+            let mut from = 0;
+            for (pos, hit) in cursor.find_all(&needle) {
+                let expected = naive_find(&haystack, from, &needle);
+                assert_eq!(Some(pos), expected, "{haystack:?} / {needle:?}");
+                assert_eq!(hit, needle);
+                from = pos + needle.len();
+            }
+            assert_eq!(naive_find(&haystack, from, &needle), None);
+
+            let pieces: Vec<_> = Cursor::new(&haystack)
+                .split_str(&needle)
+                .map(|(_, piece)| piece)
+                .collect();
+            assert_eq!(pieces, haystack.split(needle.as_str()).collect::<Vec<_>>());
+
+            let mut cursor = Cursor::new(&haystack);
+            if let Some((_, taken)) = cursor.take_until(&needle) {
+                assert_eq!(Some(taken.len()), naive_find(&haystack, 0, &needle));
+                assert_eq!(cursor.line(), naive_line(&haystack, taken.len()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_partition_point_lines() {
+        let log = "09:00 start\n09:05 load\r\n09:30 ready\n10:00 stop";
+        let cursor = Cursor::new(log);
+        assert_eq!(cursor.partition_point_lines(|l| l < "09:10"), (2, 24));
+        assert_eq!(cursor.partition_point_lines(|l| l < "09:30 ready"), (2, 24));
+        assert_eq!(cursor.partition_point_lines(|_| false), (0, 0));
+        assert_eq!(cursor.partition_point_lines(|_| true), (3, log.len()));
+        assert_eq!(cursor.partition_point_lines(|l| l < "10"), (3, 36));
+
+        let indexed = Cursor::new(log).with_line_index();
+        let mut probed = Vec::new();
+        let found = indexed.partition_point_lines(|l| {
+            probed.push(l.to_string());
+            l < "09:10"
+        });
+        assert_eq!((found, probed.len()), ((2, 24), 2));
+        assert_eq!(indexed.partition_point_lines(|_| true), (3, log.len()));
+
+        let mut cursor = Cursor::new(log).with_line_index();
+        cursor.take_until("09:");
+        cursor.next();
+        assert_eq!(cursor.partition_point_lines(|l| l < "10"), (3, 36));
+        assert_eq!(cursor.partition_point_lines(|_| false), (0, 1));
+
+        let mut cursor = Cursor::new(log);
+        cursor.take_until("09:30");
+        assert_eq!(cursor.partition_point_lines(|_| false), (2, 24));
+        cursor.seek_to_partition_point(|l| l < "10");
+        assert_eq!(
+            (cursor.line(), cursor.take_until(" ")),
+            (3, Some((36, "10:00")))
+        );
+    }
+
+    #[test]
+    fn test_partition_point_matches_linear_scan() {
+        let mut rng = XorShift(0x1405_7b7e_f767_814f);
+        for _ in 0..200 {
+            let mut keys: Vec<usize> = (0..rng.below(30)).map(|_| rng.below(50)).collect();
+            keys.sort_unstable();
+            let mut text: String = keys.iter().map(|k| format!("{k:02} x\n")).collect();
+            if rng.below(2) == 0 {
+                text.pop();
+            }
+            let key = rng.below(55);
+            let pred = |line: &str| line[..2].parse::<usize>().unwrap() < key;
+
+            let before = keys.iter().filter(|&&k| k < key).count();
+            let offset = text.split_inclusive('\n').take(before).map(str::len).sum();
+            let line = naive_line(&text, offset);
+            let cursor = Cursor::new(&text);
+            assert_eq!(cursor.partition_point_lines(pred), (line, offset));
+            let indexed = cursor.with_line_index();
+            assert_eq!(indexed.partition_point_lines(pred), (line, offset));
+        }
+    }
+}
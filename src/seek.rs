@@ -0,0 +1,297 @@
+use std::fmt;
+
+use crate::{ColumnUnit, Cursor};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeekError {
+    /// The offset lies past the end of the input.
+    OutOfRange { offset: usize, len: usize },
+    /// The offset falls inside a multibyte char.
+    NotABoundary { offset: usize },
+    /// The input has only `lines` lines.
+    LineOutOfRange { line: usize, lines: usize },
+    /// The line is only `len` columns long.
+    ColumnOutOfRange {
+        line: usize,
+        column: usize,
+        len: usize,
+    },
+}
+
+impl fmt::Display for SeekError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfRange { offset, len } => {
+                write!(
+                    f,
+                    "offset {offset} is past the end of the input ({len} bytes)"
+                )
+            }
+            Self::NotABoundary { offset } => write!(f, "offset {offset} is not a char boundary"),
+            Self::LineOutOfRange { line, lines } => {
+                write!(
+                    f,
+                    "line {line} is past the end of the input ({lines} lines)"
+                )
+            }
+            Self::ColumnOutOfRange { line, column, len } => {
+                write!(
+                    f,
+                    "column {column} is past the end of line {line} ({len} columns)"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for SeekError {}
+
+impl Cursor<'_> {
+    /// Moves the cursor to the byte offset `offset`, updating its line and
+    /// column. Fails, leaving the cursor alone, if `offset` is past the end
+    /// or inside a char.
+    pub fn seek_to_offset(&mut self, offset: usize) -> Result<(), SeekError> {
+        if offset > self.data.len() {
+            let len = self.data.len();
+            return Err(SeekError::OutOfRange { offset, len });
+        }
+        if !self.data.is_char_boundary(offset) {
+            return Err(SeekError::NotABoundary { offset });
+        }
+        self.move_to(offset);
+        Ok(())
+    }
+
+    pub(crate) fn move_to(&mut self, offset: usize) {
+        if offset >= self.offset {
+            self.advance_to(offset);
+        } else {
+            self.retreat_to(offset);
+        }
+    }
+
+    /// Byte offset of the start of the zero-based line `line`, from the line
+    /// index or else scanning from the cursor in whichever direction the
+    /// line lies.
+    pub(crate) fn line_start(&self, line: usize) -> Result<usize, SeekError> {
+        if let Some(index) = &self.line_index {
+            // The input may be a prefix of the indexed text.
+            let end = self.data.len();
+            return index.line_start(line).filter(|&start| start <= end).ok_or(
+                SeekError::LineOutOfRange {
+                    line,
+                    lines: index.line_of(end) + 1,
+                },
+            );
+        }
+        let terminator = self.terminator;
+        if line <= self.line {
+            let before = terminator.nth_before(self.data, self.offset, self.line - line);
+            return Ok(before.unwrap_or(0));
+        }
+        terminator
+            .nth_after(self.data, self.offset, line - self.line - 1)
+            .ok_or_else(|| SeekError::LineOutOfRange {
+                line,
+                lines: terminator.count(self.data, 0, self.data.len()) + 1,
+            })
+    }
+
+    /// Moves the cursor to the start of the zero-based line `line`.
+    pub fn seek_to_line(&mut self, line: usize) -> Result<(), SeekError> {
+        let start = self.line_start(line)?;
+        self.move_to(start);
+        Ok(())
+    }
+
+    /// The byte offset of the column `column` on the line starting at
+    /// `start`, or the line's length in columns if it is shorter. The end of
+    /// the line, before its terminator, is a valid column, and one inside a
+    /// char resolves to the char's start.
+    fn column_offset(&self, start: usize, column: usize) -> Result<usize, usize> {
+        self.column_offset_in(start, column, self.column_unit)
+    }
+
+    /// [`Cursor::column_offset`] with the column counted in `unit`.
+    pub(crate) fn column_offset_in(
+        &self,
+        start: usize,
+        column: usize,
+        unit: ColumnUnit,
+    ) -> Result<usize, usize> {
+        let end = self
+            .terminator
+            .nth_after(self.data, start, 0)
+            .unwrap_or(self.data.len());
+        let text = &self.data[start..end];
+        let text = self.terminator.strip(text);
+        let mut at = 0;
+        for (i, c) in text.char_indices() {
+            at += unit.width_of(c);
+            if column < at {
+                return Ok(start + i);
+            }
+        }
+        if column == at {
+            Ok(start + text.len())
+        } else {
+            Err(at)
+        }
+    }
+
+    /// Moves the cursor to a zero-based line and column, failing if the
+    /// column lies past the end of the line.
+    pub fn seek_to_line_col(&mut self, line: usize, column: usize) -> Result<(), SeekError> {
+        let start = self.line_start(line)?;
+        let offset = self
+            .column_offset(start, column)
+            .map_err(|len| SeekError::ColumnOutOfRange { line, column, len })?;
+        self.move_to(offset);
+        Ok(())
+    }
+
+    /// Like [`Cursor::seek_to_line_col`], but a column past the end of the
+    /// line moves to the end of the line.
+    pub fn seek_to_line_col_clamped(
+        &mut self,
+        line: usize,
+        column: usize,
+    ) -> Result<(), SeekError> {
+        let start = self.line_start(line)?;
+        let offset = match self.column_offset(start, column) {
+            Ok(offset) => offset,
+            Err(len) => self.column_offset(start, len).unwrap_or(start),
+        };
+        self.move_to(offset);
+        Ok(())
+    }
+
+    /// The zero-based line and column of the byte offset `offset`,
+    /// without moving the cursor. An offset past the end is clamped to it
+    /// and one inside a char is rounded down to its start.
+    pub fn position_of(&self, offset: usize) -> (usize, usize) {
+        let mut offset = offset.min(self.data.len());
+        while !self.data.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        (self.line_of(offset), self.column_at(offset))
+    }
+
+    /// The byte offset of a zero-based line and column, without moving
+    /// the cursor; the inverse of [`Cursor::position_of`]. `None` where
+    /// [`Cursor::seek_to_line_col`] would fail.
+    pub fn offset_of(&self, line: usize, column: usize) -> Option<usize> {
+        let start = self.line_start(line).ok()?;
+        self.column_offset(start, column).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::XorShift;
+
+    #[test]
+    fn test_seek_to_offset() {
+        let mut cursor = Cursor::new("ab\ncé\nd");
+        assert_eq!(cursor.seek_to_offset(6), Ok(()));
+        assert_eq!((cursor.line(), cursor.column()), (1, 2));
+        assert_eq!(cursor.seek_to_offset(1), Ok(()));
+        assert_eq!(
+            (cursor.line(), cursor.column(), cursor.peek_char()),
+            (0, 1, Some('b'))
+        );
+        assert_eq!(cursor.seek_to_offset(8), Ok(()));
+        assert_eq!(cursor.position().char_idx, 7);
+
+        assert_eq!(
+            cursor.seek_to_offset(5),
+            Err(SeekError::NotABoundary { offset: 5 })
+        );
+        assert_eq!(
+            cursor.seek_to_offset(9),
+            Err(SeekError::OutOfRange { offset: 9, len: 8 })
+        );
+        assert_eq!(cursor.offset(), 8);
+    }
+
+    #[test]
+    fn test_seek_to_line() {
+        let mut cursor = Cursor::new("zero\none\n\nthree\n");
+        assert_eq!(cursor.seek_to_line(3), Ok(()));
+        assert_eq!(
+            (cursor.offset(), cursor.line(), cursor.column()),
+            (10, 3, 0)
+        );
+        cursor.next();
+        assert_eq!(cursor.seek_to_line(3), Ok(()));
+        assert_eq!(cursor.offset(), 10);
+        assert_eq!(cursor.seek_to_line(1), Ok(()));
+        assert_eq!((cursor.offset(), cursor.line()), (5, 1));
+        assert_eq!(cursor.seek_to_line(0), Ok(()));
+        assert_eq!(cursor.position().char_idx, 0);
+
+        assert_eq!(cursor.seek_to_line(4), Ok(()));
+        assert_eq!(cursor.peek(), None);
+        assert_eq!(
+            cursor.seek_to_line(5),
+            Err(SeekError::LineOutOfRange { line: 5, lines: 5 })
+        );
+        assert_eq!(cursor.line(), 4);
+    }
+
+    #[test]
+    fn test_seek_to_line_col() {
+        let mut cursor = Cursor::new("fn f() {\r\n  é = 1;\n}");
+        assert_eq!(cursor.seek_to_line_col(1, 3), Ok(()));
+        assert_eq!(cursor.peek(), Some((14, ' ')));
+        assert_eq!(cursor.column(), 3);
+        assert_eq!(cursor.seek_to_line_col(0, 8), Ok(()));
+        assert_eq!(cursor.peek_char(), Some('\r'));
+        assert_eq!(cursor.seek_to_line_col(2, 1), Ok(()));
+        assert_eq!(cursor.peek(), None);
+
+        let err = SeekError::ColumnOutOfRange {
+            line: 0,
+            column: 9,
+            len: 8,
+        };
+        assert_eq!(cursor.seek_to_line_col(0, 9), Err(err));
+        assert_eq!(cursor.seek_to_line_col_clamped(1, 99), Ok(()));
+        assert_eq!((cursor.offset(), cursor.column()), (19, 8));
+        assert_eq!(
+            cursor.seek_to_line_col_clamped(3, 0),
+            Err(SeekError::LineOutOfRange { line: 3, lines: 3 })
+        );
+    }
+
+    #[test]
+    fn test_position_of_and_offset_of() {
+        let cursor = Cursor::new_at("ab\ncé\nd", 4).unwrap();
+        assert_eq!(cursor.position_of(0), (0, 0));
+        assert_eq!(cursor.position_of(6), (1, 2));
+        assert_eq!(cursor.position_of(5), (1, 1));
+        assert_eq!(cursor.position_of(99), (2, 1));
+        assert_eq!(cursor.offset_of(1, 2), Some(6));
+        assert_eq!(cursor.offset_of(1, 3), None);
+        assert_eq!(cursor.offset_of(3, 0), None);
+        assert_eq!(cursor.offset(), 4);
+
+        let mut rng = XorShift(3);
+        for _ in 0..100 {
+            let text = rng.string(&['a', 'é', '\n'], 0, 20);
+            let mut cursor = Cursor::new_at_end(&text);
+            if rng.next().is_multiple_of(2) {
+                cursor = cursor.with_line_index();
+            }
+            cursor.retreat_by(rng.next() as usize % 10);
+            for (offset, _) in text.char_indices().chain([(text.len(), ' ')]) {
+                let (line, column) = cursor.position_of(offset);
+                assert_eq!(cursor.offset_of(line, column), Some(offset));
+                let mut seeker = cursor.clone();
+                seeker.seek_to_offset(offset).unwrap();
+                assert_eq!((seeker.line(), seeker.column()), (line, column));
+            }
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use crate::Cursor;
+
+fn ends_sentence(c: char) -> bool {
+    matches!(c, '.' | '!' | '?')
+}
+
+/// Punctuation that may close a sentence after its terminator, as in
+/// `"Stop!"` or `(see above.)`.
+fn closes_sentence(c: char) -> bool {
+    ends_sentence(c) || matches!(c, '"' | '\'' | ')' | ']' | '\u{201d}' | '\u{2019}')
+}
+
+/// Byte length of the first sentence of `text`: up to a run of `.`, `!` or
+/// `?` and any closing punctuation that is followed by the end, or by
+/// whitespace and then anything but a lowercase letter.
+fn sentence_len(text: &str) -> usize {
+    let mut chars = text.char_indices().peekable();
+    while let Some((_, c)) = chars.next() {
+        if !ends_sentence(c) {
+            continue;
+        }
+        while chars.next_if(|&(_, c)| closes_sentence(c)).is_some() {}
+        match chars.peek() {
+            None => break,
+            Some(&(i, c)) if c.is_whitespace() => {
+                let next = text[i..].trim_start().chars().next();
+                if !next.is_some_and(char::is_lowercase) {
+                    return i;
+                }
+            }
+            _ => {}
+        }
+    }
+    text.trim_end().len()
+}
+
+/// Iterator over the remaining sentences; see [`Cursor::sentences`].
+pub struct CursorSentences<'a, 'b> {
+    cursor: &'b mut Cursor<'a>,
+    #[cfg(feature = "segmentation")]
+    unicode: bool,
+}
+
+impl CursorSentences<'_, '_> {
+    /// Splits at the sentence boundaries of UAX #29 instead of at
+    /// terminating punctuation.
+    #[cfg(feature = "segmentation")]
+    pub fn unicode(mut self, unicode: bool) -> Self {
+        self.unicode = unicode;
+        self
+    }
+}
+
+impl<'a> Cursor<'a> {
+    /// Iterates over the remaining sentences, each without the whitespace
+    /// around it. A sentence ends at `.`, `!` or `?`, with any closing quotes
+    /// or brackets, when whitespace follows and the next word doesn't start
+    /// in lowercase; so neither `3.14` nor `e.g. this` ends one, but `Dr.
+    /// Who` does.
+    pub fn sentences(&mut self) -> CursorSentences<'a, '_> {
+        CursorSentences {
+            cursor: self,
+            #[cfg(feature = "segmentation")]
+            unicode: false,
+        }
+    }
+}
+
+impl<'a> Iterator for CursorSentences<'a, '_> {
+    type Item = (usize, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.cursor.skip_whitespace();
+        let start = self.cursor.offset;
+        let rest = self.cursor.forward();
+        if rest.is_empty() {
+            return None;
+        }
+        #[cfg(feature = "segmentation")]
+        if self.unicode {
+            use unicode_segmentation::UnicodeSegmentation;
+            let sentence = rest.split_sentence_bounds().next()?;
+            self.cursor.advance_to(start + sentence.len());
+            return Some((start, sentence.trim_end()));
+        }
+        let len = sentence_len(rest);
+        self.cursor.advance_to(start + len);
+        Some((start, &rest[..len]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "It costs 3.50, e.g. now.  \"Really?!\" she asked.\n\nYes (mostly.) Ok";
+
+    #[test]
+    fn test_sentences() {
+        let mut cursor = Cursor::new(TEXT);
+        let sentences: Vec<_> = cursor.sentences().collect();
+        assert_eq!(
+            sentences,
+            [
+                (0, "It costs 3.50, e.g. now."),
+                (26, "\"Really?!\" she asked."),
+                (49, "Yes (mostly.)"),
+                (63, "Ok"),
+            ]
+        );
+        assert!(cursor.is_at_end());
+        assert_eq!(Cursor::new(" \n ").sentences().next(), None);
+    }
+
+    #[cfg(feature = "segmentation")]
+    #[test]
+    fn test_unicode_sentences() {
+        let mut cursor = Cursor::new("Mr. Smith left. He said “Hi!” Then 3.5 km.");
+        let sentences: Vec<_> = cursor.sentences().unicode(true).map(|(_, s)| s).collect();
+        assert_eq!(
+            sentences,
+            ["Mr.", "Smith left.", "He said “Hi!”", "Then 3.5 km."]
+        );
+    }
+}
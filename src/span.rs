@@ -0,0 +1,280 @@
+use std::ops::Range;
+
+use crate::{Checkpoint, Cursor};
+
+/// A byte range `start..end` into the cursor's input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub const fn new(start: usize, end: usize) -> Self {
+        Self { start, end }
+    }
+
+    pub const fn len(&self) -> usize {
+        self.end - self.start
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+}
+
+impl From<Range<usize>> for Span {
+    fn from(range: Range<usize>) -> Self {
+        Self::new(range.start, range.end)
+    }
+}
+
+impl From<Span> for Range<usize> {
+    fn from(span: Span) -> Self {
+        span.start..span.end
+    }
+}
+
+impl<'a> Cursor<'a> {
+    /// The span between `checkpoint` and the cursor, in either order.
+    pub fn span_since(&self, checkpoint: &Checkpoint) -> Span {
+        let at = checkpoint.offset();
+        Span::new(at.min(self.offset), at.max(self.offset))
+    }
+
+    /// The input text covered by `span`. Panics if `span` is out of range or
+    /// splits a char, like indexing a `str`.
+    pub fn slice(&self, span: Span) -> &'a str {
+        &self.data[span.start..span.end]
+    }
+}
+
+/// A sorted set of disjoint, non-empty spans.
+///
+/// Overlapping spans are always merged. Spans that only touch (`0..2` and
+/// `2..4`) are merged too, unless the set was built with
+/// [`SpanSet::keep_adjacent`]. Empty spans cover no offsets and are dropped.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SpanSet {
+    spans: Vec<Span>,
+    merge_adjacent: bool,
+}
+
+impl SpanSet {
+    fn build(spans: impl IntoIterator<Item = Span>, merge_adjacent: bool) -> Self {
+        let mut sorted: Vec<Span> = spans.into_iter().filter(|s| !s.is_empty()).collect();
+        sorted.sort_unstable();
+        let mut merged: Vec<Span> = Vec::with_capacity(sorted.len());
+        for span in sorted {
+            match merged.last_mut() {
+                Some(last)
+                    if span.start < last.end || (merge_adjacent && span.start == last.end) =>
+                {
+                    last.end = last.end.max(span.end);
+                }
+                _ => merged.push(span),
+            }
+        }
+        Self {
+            spans: merged,
+            merge_adjacent,
+        }
+    }
+
+    /// Builds a set that merges overlapping spans but keeps touching ones
+    /// apart.
+    pub fn keep_adjacent<S: Into<Span>>(spans: impl IntoIterator<Item = S>) -> Self {
+        Self::build(spans.into_iter().map(Into::into), false)
+    }
+
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    pub fn as_slice(&self) -> &[Span] {
+        &self.spans
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Span> {
+        self.spans.iter()
+    }
+
+    pub fn contains(&self, offset: usize) -> bool {
+        let idx = self.spans.partition_point(|s| s.end <= offset);
+        self.spans.get(idx).is_some_and(|s| s.start <= offset)
+    }
+
+    /// The spans that share at least one offset with `range`. An empty range
+    /// overlaps only a span that strictly surrounds it.
+    pub fn overlapping(&self, range: impl Into<Span>) -> impl Iterator<Item = &Span> {
+        let range = range.into();
+        let idx = self.spans.partition_point(|s| s.end <= range.start);
+        self.spans[idx..]
+            .iter()
+            .take_while(move |s| s.start < range.end)
+            .filter(move |s| !range.is_empty() || s.start < range.start)
+    }
+
+    /// The gaps between the spans, clipped to `within`.
+    pub fn invert(&self, within: impl Into<Span>) -> SpanSet {
+        let within = within.into();
+        let mut gaps = Vec::new();
+        let mut at = within.start;
+        for span in self.overlapping(within) {
+            if span.start > at {
+                gaps.push(Span::new(at, span.start));
+            }
+            at = at.max(span.end);
+        }
+        if at < within.end {
+            gaps.push(Span::new(at, within.end));
+        }
+        Self::build(gaps, self.merge_adjacent)
+    }
+
+    pub fn union(&self, other: &SpanSet) -> SpanSet {
+        let spans = self.spans.iter().chain(&other.spans).copied();
+        Self::build(spans, self.merge_adjacent)
+    }
+
+    pub fn intersection(&self, other: &SpanSet) -> SpanSet {
+        let (mut a, mut b) = (self.spans.iter().peekable(), other.spans.iter().peekable());
+        let mut spans = Vec::new();
+        while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+            let (start, end) = (x.start.max(y.start), x.end.min(y.end));
+            if start < end {
+                spans.push(Span::new(start, end));
+            }
+            if x.end < y.end {
+                a.next();
+            } else {
+                b.next();
+            }
+        }
+        Self::build(spans, self.merge_adjacent)
+    }
+}
+
+impl<S: Into<Span>> FromIterator<S> for SpanSet {
+    fn from_iter<T: IntoIterator<Item = S>>(spans: T) -> Self {
+        Self::build(spans.into_iter().map(Into::into), true)
+    }
+}
+
+impl<'s> IntoIterator for &'s SpanSet {
+    type Item = &'s Span;
+    type IntoIter = std::slice::Iter<'s, Span>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::XorShift;
+
+    #[test]
+    fn test_span_since() {
+        let mut cursor = Cursor::new("let name = 1;");
+        let origin = cursor.checkpoint();
+        cursor.eat_str("let ");
+        let start = cursor.checkpoint();
+        cursor.consume_while(char::is_alphanumeric);
+        let span = cursor.span_since(&start);
+        assert_eq!(span, Span::new(4, 8));
+        assert_eq!(cursor.slice(span), "name");
+
+        cursor.restore(origin);
+        assert_eq!(cursor.span_since(&start), Span::new(0, 4));
+    }
+
+    fn spans(set: &SpanSet) -> Vec<Range<usize>> {
+        set.iter().map(|&s| s.into()).collect()
+    }
+
+    fn random_spans(rng: &mut XorShift, n: usize) -> Vec<Range<usize>> {
+        (0..n)
+            .map(|_| {
+                let start = rng.below(60);
+                start..start + rng.below(6)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_merge_edge_cases() {
+        let set: SpanSet = [5..8, 0..2, 2..4, 7..10, 12..12].into_iter().collect();
+        assert_eq!(spans(&set), [0..4, 5..10]);
+
+        let apart = SpanSet::keep_adjacent([5..8, 0..2, 2..4, 7..10, 3..3]);
+        assert_eq!(spans(&apart), [0..2, 2..4, 5..10]);
+        assert!(SpanSet::from_iter([Span::new(4, 4)]).is_empty());
+    }
+
+    #[test]
+    fn test_queries() {
+        let set: SpanSet = [0..2, 5..10, 20..30].into_iter().collect();
+        assert!(set.contains(0) && set.contains(9) && set.contains(25));
+        assert!(!set.contains(2) && !set.contains(10) && !set.contains(30));
+
+        let hits = |r: Range<usize>| {
+            set.overlapping(r)
+                .map(|s| (s.start, s.end))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(hits(1..6), [(0, 2), (5, 10)]);
+        assert_eq!(hits(10..20), []);
+        assert_eq!(hits(9..21), [(5, 10), (20, 30)]);
+        assert_eq!(hits(7..7), [(5, 10)]);
+        assert_eq!(hits(5..5), []);
+
+        assert_eq!(spans(&set.invert(1..40)), [2..5, 10..20, 30..40]);
+        assert!(spans(&set.invert(6..8)).is_empty());
+    }
+
+    #[test]
+    fn test_union_and_intersection() {
+        let a: SpanSet = [0..5, 10..15].into_iter().collect();
+        let b: SpanSet = [3..12, 15..20].into_iter().collect();
+        assert_eq!(a.union(&b).as_slice(), [Span::new(0, 20)]);
+        assert_eq!(spans(&a.intersection(&b)), [3..5, 10..12]);
+        assert!(a
+            .intersection(&SpanSet::from_iter([Span::new(5, 10)]))
+            .is_empty());
+    }
+
+    #[test]
+    fn test_random_sets_are_normalized() {
+        let mut rng = XorShift(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..200 {
+            let raw = random_spans(&mut rng, 12);
+            let set: SpanSet = raw.iter().cloned().collect();
+            let again: SpanSet = set.iter().copied().collect();
+            assert_eq!(set, again);
+            assert_eq!(set.union(&set), set);
+            assert_eq!(set.intersection(&set), set);
+            assert!(set.as_slice().windows(2).all(|w| w[0].end < w[1].start));
+
+            for offset in 0..70 {
+                let covered = raw.iter().any(|r| r.contains(&offset));
+                assert_eq!(set.contains(offset), covered);
+                assert_eq!(set.invert(0..70).contains(offset), !covered);
+            }
+
+            let other: SpanSet = random_spans(&mut rng, 8).into_iter().collect();
+            let both = set.intersection(&other);
+            let either = set.union(&other);
+            for offset in 0..70 {
+                let (x, y) = (set.contains(offset), other.contains(offset));
+                assert_eq!(both.contains(offset), x && y);
+                assert_eq!(either.contains(offset), x || y);
+            }
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use crate::Cursor;
+
+fn same_char(a: char, b: char, ignore_case: bool) -> bool {
+    a == b || (ignore_case && a.to_lowercase().eq(b.to_lowercase()))
+}
+
+/// Reusable rows for [`BoundedDistance::distance`], so that scoring many
+/// candidates allocates only once.
+#[derive(Default)]
+struct BoundedDistance {
+    word: Vec<char>,
+    before: Vec<usize>,
+    prev: Vec<usize>,
+    row: Vec<usize>,
+}
+
+impl BoundedDistance {
+    /// The optimal string alignment distance (Damerau-Levenshtein with
+    /// adjacent transpositions) between the stored word and `candidate`, or
+    /// `None` as soon as it is known to exceed `max`.
+    fn distance(&mut self, candidate: &str, max: usize, ignore_case: bool) -> Option<usize> {
+        let n = self.word.len();
+        if candidate.chars().count().abs_diff(n) > max {
+            return None;
+        }
+        self.before.clear();
+        self.before.resize(n + 1, 0);
+        self.prev.clear();
+        self.prev.extend(0..=n);
+        let mut last = None;
+        for (i, c) in candidate.chars().enumerate() {
+            self.row.clear();
+            self.row.push(i + 1);
+            for j in 0..n {
+                let w = self.word[j];
+                let cost = usize::from(!same_char(c, w, ignore_case));
+                let mut best = (self.prev[j] + cost)
+                    .min(self.prev[j + 1] + 1)
+                    .min(self.row[j] + 1);
+                let transposed = j > 0
+                    && last.is_some_and(|l| same_char(l, w, ignore_case))
+                    && same_char(c, self.word[j - 1], ignore_case);
+                if transposed {
+                    best = best.min(self.before[j - 1] + 1);
+                }
+                self.row.push(best);
+            }
+            if self.row.iter().min().is_some_and(|&m| m > max) {
+                return None;
+            }
+            std::mem::swap(&mut self.before, &mut self.prev);
+            std::mem::swap(&mut self.prev, &mut self.row);
+            last = Some(c);
+        }
+        Some(self.prev[n]).filter(|&d| d <= max)
+    }
+}
+
+impl Cursor<'_> {
+    /// Suggests the candidate closest to the identifier at the cursor (after
+    /// any whitespace), for "did you mean" messages. Only candidates within
+    /// `max_distance` edits count, where swapping two adjacent chars is
+    /// one edit; ties go to the earliest candidate. The cursor does not move.
+    pub fn suggest_similar<'c>(
+        &self,
+        candidates: &[&'c str],
+        max_distance: usize,
+    ) -> Option<&'c str> {
+        self.suggest(candidates, max_distance, false)
+    }
+
+    /// Like [`Cursor::suggest_similar`], but comparing chars without regard
+    /// to case.
+    pub fn suggest_similar_ignore_case<'c>(
+        &self,
+        candidates: &[&'c str],
+        max_distance: usize,
+    ) -> Option<&'c str> {
+        self.suggest(candidates, max_distance, true)
+    }
+
+    fn suggest<'c>(
+        &self,
+        candidates: &[&'c str],
+        max: usize,
+        ignore_case: bool,
+    ) -> Option<&'c str> {
+        let word = self.forward().trim_start();
+        let len = word
+            .find(|c: char| !c.is_alphanumeric() && c != '_')
+            .unwrap_or(word.len());
+        if len == 0 {
+            return None;
+        }
+        let mut scorer = BoundedDistance {
+            word: word[..len].chars().collect(),
+            ..BoundedDistance::default()
+        };
+        let mut best: Option<(usize, &'c str)> = None;
+        for &candidate in candidates {
+            if best.is_some_and(|(d, _)| d == 0) {
+                break;
+            }
+            // Later candidates must do strictly better to win a tie.
+            let limit = best.map_or(max, |(d, _)| d - 1);
+            if let Some(d) = scorer.distance(candidate, limit, ignore_case) {
+                best = Some((d, candidate));
+            }
+        }
+        best.map(|(_, candidate)| candidate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParseError;
+
+    const KEYWORDS: &[&str] = &["if", "else", "while", "for", "return", "white"];
+
+    fn distance(a: &str, b: &str) -> Option<usize> {
+        let mut scorer = BoundedDistance {
+            word: a.chars().collect(),
+            ..BoundedDistance::default()
+        };
+        scorer.distance(b, 10, false)
+    }
+
+    #[test]
+    fn test_distance() {
+        assert_eq!(distance("whlie", "while"), Some(1));
+        assert_eq!(distance("ab", "ba"), Some(1));
+        assert_eq!(distance("kitten", "sitting"), Some(3));
+        assert_eq!(distance("", "abc"), Some(3));
+        assert_eq!(distance("ca", "abc"), Some(3));
+    }
+
+    #[test]
+    fn test_suggest_similar() {
+        let cursor = Cursor::new("  whlie (x) {}");
+        assert_eq!(cursor.suggest_similar(KEYWORDS, 2), Some("while"));
+        assert_eq!(cursor.peek(), Some((0, ' ')));
+        assert_eq!(
+            Cursor::new("retrun").suggest_similar(KEYWORDS, 1),
+            Some("return")
+        );
+        assert_eq!(Cursor::new("banana").suggest_similar(KEYWORDS, 2), None);
+        assert_eq!(Cursor::new("(").suggest_similar(KEYWORDS, 2), None);
+    }
+
+    #[test]
+    fn test_ties_and_case() {
+        // "whihe" is one edit from both "while" and "white"; the earlier wins.
+        assert_eq!(
+            Cursor::new("whihe").suggest_similar(KEYWORDS, 1),
+            Some("while")
+        );
+        let shouted = Cursor::new("WHLIE");
+        assert_eq!(shouted.suggest_similar(KEYWORDS, 1), None);
+        assert_eq!(
+            shouted.suggest_similar_ignore_case(KEYWORDS, 1),
+            Some("while")
+        );
+    }
+
+    #[test]
+    fn test_suggestion_in_error() {
+        let cursor = Cursor::new("whlie x");
+        let err = ParseError::new("unknown keyword `whlie`", 0, 0, 0)
+            .with_suggestion(cursor.suggest_similar(KEYWORDS, 2).unwrap());
+        assert_eq!(
+            err.to_string(),
+            "unknown keyword `whlie`, did you mean `while`? at 1:1"
+        );
+    }
+}
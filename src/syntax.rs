@@ -0,0 +1,302 @@
+use crate::{Cursor, LineTerminator};
+
+/// A string literal delimiter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StringDelimiter {
+    pub quote: char,
+    /// The char that escapes the next one, or `None` for raw strings.
+    pub escape: Option<char>,
+    /// Whether the string may span lines; otherwise a newline ends it.
+    pub multiline: bool,
+}
+
+/// The lexical rules a [`ContextTracker`] follows.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SyntaxConfig {
+    pub strings: Vec<StringDelimiter>,
+    pub line_comment: Option<String>,
+    pub block_comment: Option<(String, String)>,
+    pub nested_comments: bool,
+}
+
+impl SyntaxConfig {
+    /// `"..."` and `'...'` strings with `\` escapes, `//` and `/* */`
+    /// comments.
+    pub fn c_like() -> Self {
+        let string = |quote| StringDelimiter {
+            quote,
+            escape: Some('\\'),
+            multiline: false,
+        };
+        Self {
+            strings: vec![string('"'), string('\'')],
+            line_comment: Some("//".to_string()),
+            block_comment: Some(("/*".to_string(), "*/".to_string())),
+            nested_comments: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyntaxContext {
+    Code,
+    InString(char),
+    InLineComment,
+    /// Inside a block comment, nested `depth` levels deep.
+    InBlockComment(usize),
+}
+
+/// Tracks whether the position after the chars fed so far is inside a
+/// string or a comment.
+#[derive(Debug, Clone)]
+pub struct ContextTracker {
+    config: SyntaxConfig,
+    state: SyntaxContext,
+    escaped: bool,
+    /// The chars fed since the last state change, enough to recognise the
+    /// longest multi-char delimiter.
+    recent: String,
+    window: usize,
+    terminator: LineTerminator,
+}
+
+impl ContextTracker {
+    pub fn new(config: SyntaxConfig) -> Self {
+        let window = config
+            .line_comment
+            .iter()
+            .chain(config.block_comment.iter().flat_map(|(a, b)| [a, b]))
+            .map(|delim| delim.chars().count())
+            .max()
+            .unwrap_or(1);
+        Self {
+            config,
+            state: SyntaxContext::Code,
+            escaped: false,
+            recent: String::new(),
+            window,
+            terminator: LineTerminator::Lf,
+        }
+    }
+
+    /// Ends line comments and single-line strings where `terminator` says
+    /// lines end, rather than only at `\n`.
+    pub fn with_terminator(mut self, terminator: LineTerminator) -> Self {
+        self.terminator = terminator;
+        self
+    }
+
+    pub const fn state(&self) -> SyntaxContext {
+        self.state
+    }
+
+    fn enter(&mut self, state: SyntaxContext) {
+        self.state = state;
+        self.recent.clear();
+    }
+
+    fn remember(&mut self, c: char) {
+        self.recent.push(c);
+        if self.recent.chars().count() > self.window {
+            self.recent.remove(0);
+        }
+    }
+
+    fn ends_with(&self, delim: Option<&String>) -> bool {
+        delim.is_some_and(|delim| self.recent.ends_with(delim.as_str()))
+    }
+
+    fn at_block_open(&self) -> bool {
+        self.ends_with(self.config.block_comment.as_ref().map(|(open, _)| open))
+    }
+
+    fn at_block_close(&self) -> bool {
+        self.ends_with(self.config.block_comment.as_ref().map(|(_, close)| close))
+    }
+
+    pub fn feed(&mut self, c: char) {
+        match self.state {
+            SyntaxContext::Code => {
+                self.remember(c);
+                if self.ends_with(self.config.line_comment.as_ref()) {
+                    self.enter(SyntaxContext::InLineComment);
+                } else if self.at_block_open() {
+                    self.enter(SyntaxContext::InBlockComment(1));
+                } else if self.config.strings.iter().any(|s| s.quote == c) {
+                    self.escaped = false;
+                    self.enter(SyntaxContext::InString(c));
+                }
+            }
+            SyntaxContext::InString(quote) => {
+                let delim = self.config.strings.iter().find(|s| s.quote == quote);
+                let (escape, multiline) = delim.map_or((None, false), |d| (d.escape, d.multiline));
+                if self.escaped {
+                    self.escaped = false;
+                } else if Some(c) == escape {
+                    self.escaped = true;
+                } else if c == quote || (self.terminator.can_end_line(c) && !multiline) {
+                    self.enter(SyntaxContext::Code);
+                }
+            }
+            SyntaxContext::InLineComment => {
+                if self.terminator.can_end_line(c) {
+                    self.enter(SyntaxContext::Code);
+                }
+            }
+            SyntaxContext::InBlockComment(depth) => {
+                self.remember(c);
+                if self.at_block_close() {
+                    self.enter(match depth {
+                        1 => SyntaxContext::Code,
+                        _ => SyntaxContext::InBlockComment(depth - 1),
+                    });
+                } else if self.config.nested_comments && self.at_block_open() {
+                    self.enter(SyntaxContext::InBlockComment(depth + 1));
+                }
+            }
+        }
+    }
+
+    pub fn feed_str(&mut self, text: &str) {
+        text.chars().for_each(|c| self.feed(c));
+    }
+}
+
+impl Cursor<'_> {
+    /// Scans from the start of the input to the cursor and reports whether
+    /// the cursor is inside a string or comment.
+    pub fn syntax_context(&self, config: &SyntaxConfig) -> SyntaxContext {
+        let mut tracker = ContextTracker::new(config.clone()).with_terminator(self.terminator);
+        tracker.feed_str(self.backward());
+        tracker.state()
+    }
+
+    /// Like [`Cursor::syntax_context`], but only scans the current line, so
+    /// it is cheaper and assumes no multi-line string or comment is open at
+    /// the start of the line.
+    pub fn line_syntax_context(&self, config: &SyntaxConfig) -> SyntaxContext {
+        let line_start = self.terminator.line_start(self.data, self.offset);
+        let mut tracker = ContextTracker::new(config.clone()).with_terminator(self.terminator);
+        tracker.feed_str(&self.data[line_start..self.offset]);
+        tracker.state()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context_at(text: &str, at: &str, config: &SyntaxConfig) -> SyntaxContext {
+        let mut cursor = Cursor::new(text);
+        cursor.take_until(at).expect("marker");
+        cursor.syntax_context(config)
+    }
+
+    #[test]
+    fn test_strings_and_escapes() {
+        let c = SyntaxConfig::c_like();
+        let text = r#"x = "a \" b|" + 'c|' |"#;
+        assert_eq!(context_at(text, " b|", &c), SyntaxContext::InString('"'));
+        assert_eq!(context_at(text, "c|", &c), SyntaxContext::InString('\''));
+        assert_eq!(context_at(text, " + ", &c), SyntaxContext::Code);
+        assert_eq!(context_at(r#""a\\" |"#, " |", &c), SyntaxContext::Code);
+        assert_eq!(context_at("\"open\nnext", "next", &c), SyntaxContext::Code);
+    }
+
+    #[test]
+    fn test_comments() {
+        let c = SyntaxConfig::c_like();
+        let text = "a // \"not a string\nb /* c */ d /* never closed\ne";
+        assert_eq!(context_at(text, " \"not", &c), SyntaxContext::InLineComment);
+        assert_eq!(context_at(text, "b ", &c), SyntaxContext::Code);
+        assert_eq!(
+            context_at(text, " c ", &c),
+            SyntaxContext::InBlockComment(1)
+        );
+        assert_eq!(context_at(text, " d", &c), SyntaxContext::Code);
+        assert_eq!(
+            context_at(text, "\ne", &c),
+            SyntaxContext::InBlockComment(1)
+        );
+        assert_eq!(context_at("\"// x\" y", " y", &c), SyntaxContext::Code);
+        assert_eq!(
+            context_at("/*/ x", " x", &c),
+            SyntaxContext::InBlockComment(1)
+        );
+    }
+
+    #[test]
+    fn test_follows_line_terminator() {
+        let c = SyntaxConfig::c_like();
+        for text in ["a // x\rb \"s\rc", "a // x\u{2028}b \"s\u{2028}c"] {
+            let mut cursor = Cursor::new(text).with_line_terminator(LineTerminator::Unicode);
+            cursor.take_until("b").unwrap();
+            assert_eq!(cursor.syntax_context(&c), SyntaxContext::Code);
+            assert_eq!(cursor.line_syntax_context(&c), SyntaxContext::Code);
+            cursor.take_until("c").unwrap();
+            assert_eq!(cursor.syntax_context(&c), SyntaxContext::Code);
+            assert_eq!(cursor.line_syntax_context(&c), SyntaxContext::Code);
+
+            let lf = Cursor::new_at(text, cursor.offset()).unwrap();
+            assert_eq!(lf.syntax_context(&c), SyntaxContext::InLineComment);
+        }
+    }
+
+    #[test]
+    fn test_nested_comments_and_raw_strings() {
+        let config = SyntaxConfig {
+            strings: vec![StringDelimiter {
+                quote: '`',
+                escape: None,
+                multiline: true,
+            }],
+            line_comment: Some("#".to_string()),
+            block_comment: Some(("(*".to_string(), "*)".to_string())),
+            nested_comments: true,
+        };
+        let text = "(* a (* b *) c *) `raw \\ d\n e` f";
+        assert_eq!(
+            context_at(text, " b", &config),
+            SyntaxContext::InBlockComment(2)
+        );
+        assert_eq!(
+            context_at(text, " c", &config),
+            SyntaxContext::InBlockComment(1)
+        );
+        assert_eq!(context_at(text, " `raw", &config), SyntaxContext::Code);
+        assert_eq!(
+            context_at(text, " d", &config),
+            SyntaxContext::InString('`')
+        );
+        assert_eq!(
+            context_at(text, " e", &config),
+            SyntaxContext::InString('`')
+        );
+        assert_eq!(context_at(text, " f", &config), SyntaxContext::Code);
+    }
+
+    #[test]
+    fn test_feed_incrementally() {
+        let mut tracker = ContextTracker::new(SyntaxConfig::c_like());
+        let mut states = Vec::new();
+        for c in "a/*b*/".chars() {
+            tracker.feed(c);
+            states.push(tracker.state());
+        }
+        use SyntaxContext::*;
+        let expected = [
+            Code,
+            Code,
+            InBlockComment(1),
+            InBlockComment(1),
+            InBlockComment(1),
+            Code,
+        ];
+        assert_eq!(states, expected);
+
+        let mut cursor = Cursor::new("x = 1 // note");
+        cursor.take_until("note");
+        let config = SyntaxConfig::c_like();
+        assert_eq!(cursor.line_syntax_context(&config), InLineComment);
+    }
+}
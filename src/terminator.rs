@@ -0,0 +1,204 @@
+use crate::{Cursor, LineIndex};
+
+/// Which chars end a line, set with [`Cursor::with_line_terminator`].
+///
+/// Under every policy `\r\n` is a single line break: a `\r` directly
+/// followed by `\n` is part of the line, which ends at the `\n`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LineTerminator {
+    /// Only `\n`. The default.
+    #[default]
+    Lf,
+    /// `\n`, `\r\n` and a lone `\r`.
+    Ascii,
+    /// Everything [`LineTerminator::Ascii`] accepts, plus NEL (U+0085), the
+    /// line separator (U+2028) and the paragraph separator (U+2029).
+    Unicode,
+}
+
+impl LineTerminator {
+    /// Whether `c`, found at byte `at` of `data`, ends a line.
+    pub(crate) fn ends_line(self, data: &str, at: usize, c: char) -> bool {
+        self.can_end_line(c) && !(c == '\r' && data.as_bytes().get(at + 1) == Some(&b'\n'))
+    }
+
+    /// Whether `c` ends a line when it is not the `\r` of a `\r\n`, for
+    /// scanning a char at a time.
+    pub(crate) fn can_end_line(self, c: char) -> bool {
+        match c {
+            '\n' => true,
+            '\r' => self != Self::Lf,
+            '\u{85}' | '\u{2028}' | '\u{2029}' => self == Self::Unicode,
+            _ => false,
+        }
+    }
+
+    /// The number of line breaks in `data[start..end]`.
+    pub(crate) fn count(self, data: &str, start: usize, end: usize) -> usize {
+        match self {
+            Self::Lf => memchr::memchr_iter(b'\n', &data.as_bytes()[start..end]).count(),
+            _ => data[start..end]
+                .char_indices()
+                .filter(|&(i, c)| self.ends_line(data, start + i, c))
+                .count(),
+        }
+    }
+
+    /// The offset just past the `n`th line break at or after `from`.
+    pub(crate) fn nth_after(self, data: &str, from: usize, n: usize) -> Option<usize> {
+        match self {
+            Self::Lf => memchr::memchr_iter(b'\n', &data.as_bytes()[from..])
+                .nth(n)
+                .map(|i| from + i + 1),
+            _ => data[from..]
+                .char_indices()
+                .filter(|&(i, c)| self.ends_line(data, from + i, c))
+                .nth(n)
+                .map(|(i, c)| from + i + c.len_utf8()),
+        }
+    }
+
+    /// The offset just past the `n`th line break before `at`, counting back
+    /// from `at`.
+    pub(crate) fn nth_before(self, data: &str, at: usize, n: usize) -> Option<usize> {
+        match self {
+            Self::Lf => memchr::memrchr_iter(b'\n', &data.as_bytes()[..at])
+                .nth(n)
+                .map(|i| i + 1),
+            _ => data[..at]
+                .char_indices()
+                .rev()
+                .filter(|&(i, c)| self.ends_line(data, i, c))
+                .nth(n)
+                .map(|(i, c)| i + c.len_utf8()),
+        }
+    }
+
+    /// `line` without its terminator, taking `\r\n` as a whole.
+    pub(crate) fn strip(self, line: &str) -> &str {
+        if let Some(line) = line.strip_suffix('\n') {
+            return line.strip_suffix('\r').unwrap_or(line);
+        }
+        match line.char_indices().next_back() {
+            Some((i, c)) if self.ends_line(line, i, c) => &line[..i],
+            _ => line,
+        }
+    }
+
+    /// The offset where the line containing `at` starts.
+    pub(crate) fn line_start(self, data: &str, at: usize) -> usize {
+        self.nth_before(data, at, 0).unwrap_or(0)
+    }
+}
+
+impl<'a> Cursor<'a> {
+    /// Uses `terminator` to decide where lines end, recomputing the cursor's
+    /// line and column for it.
+    pub fn with_line_terminator(mut self, terminator: LineTerminator) -> Self {
+        self.terminator = terminator;
+        if self.line_index.is_some() {
+            let index = LineIndex::with_terminator(self.data, terminator);
+            self.line_index = Some(std::sync::Arc::new(index));
+        }
+        self.line = terminator.count(self.data, 0, self.offset);
+        self.column = self.column_at(self.offset);
+        self
+    }
+
+    pub const fn line_terminator(&self) -> LineTerminator {
+        self.terminator
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::XorShift;
+
+    const MIXED: &str = "a\r\nb\rc\u{2028}d\ne";
+
+    fn line_col(cursor: &Cursor) -> (usize, usize) {
+        (cursor.line(), cursor.column())
+    }
+
+    #[test]
+    fn test_crlf_is_one_break() {
+        let mut cursor = Cursor::new("a\r\nb").with_line_terminator(LineTerminator::Ascii);
+        cursor.next();
+        cursor.next();
+        assert_eq!(line_col(&cursor), (0, 2));
+        cursor.next();
+        assert_eq!(line_col(&cursor), (1, 0));
+        cursor.prev();
+        assert_eq!(line_col(&cursor), (0, 2));
+        cursor.prev();
+        assert_eq!(line_col(&cursor), (0, 1));
+    }
+
+    #[test]
+    fn test_lines_per_policy() {
+        let count = |terminator| {
+            let mut cursor = Cursor::new(MIXED).with_line_terminator(terminator);
+            cursor.lines().count()
+        };
+        assert_eq!(count(LineTerminator::Lf), 3);
+        assert_eq!(count(LineTerminator::Ascii), 4);
+        assert_eq!(count(LineTerminator::Unicode), 5);
+
+        let mut cursor = Cursor::new(MIXED).with_line_terminator(LineTerminator::Unicode);
+        let lines: Vec<_> = cursor.lines().map(|(_, n, line)| (n, line)).collect();
+        assert_eq!(lines, [(0, "a"), (1, "b"), (2, "c"), (3, "d"), (4, "e")]);
+        assert_eq!(line_col(&cursor), (4, 1));
+        let back: Vec<_> = cursor.lines_rev().map(|(n, _)| n).collect();
+        assert_eq!(back, [4, 3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn test_switching_recomputes_position() {
+        let cursor = Cursor::new_at_end(MIXED);
+        assert_eq!(line_col(&cursor), (2, 1));
+        let cursor = cursor.with_line_terminator(LineTerminator::Unicode);
+        assert_eq!(line_col(&cursor), (4, 1));
+        assert_eq!(cursor.line_terminator(), LineTerminator::Unicode);
+    }
+
+    #[test]
+    fn test_seek_to_line() {
+        let mut cursor = Cursor::new(MIXED).with_line_terminator(LineTerminator::Ascii);
+        assert_eq!(cursor.seek_to_line(2), Ok(()));
+        assert_eq!(cursor.peek_char(), Some('c'));
+        assert_eq!(cursor.seek_to_line(1), Ok(()));
+        assert_eq!(cursor.peek(), Some((3, 'b')));
+        assert!(cursor.seek_to_line(4).is_err());
+    }
+
+    #[test]
+    fn test_bulk_moves_match_stepping() {
+        let alphabet = ['a', '\n', '\r', '\u{85}', '\u{2029}', 'é'];
+        let mut rng = XorShift(0x9e37_79b9_7f4a_7c15);
+        for terminator in [
+            LineTerminator::Lf,
+            LineTerminator::Ascii,
+            LineTerminator::Unicode,
+        ] {
+            for _ in 0..50 {
+                let text = rng.string(&alphabet, 0, 40);
+                let mut stepped = Cursor::new(&text).with_line_terminator(terminator);
+                let mut positions = vec![stepped.position()];
+                while stepped.next().is_some() {
+                    positions.push(stepped.position());
+                }
+                let mut bulk = Cursor::new(&text).with_line_terminator(terminator);
+                for &expected in positions.iter().rev() {
+                    bulk.seek_to_offset(expected.byte).unwrap();
+                    assert_eq!(bulk.position(), expected, "{text:?}");
+                }
+                while let Some(back) = positions.pop() {
+                    assert_eq!(stepped.position(), back, "{text:?}");
+                    stepped.prev();
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,23 @@
+/// A small deterministic generator for randomized tests.
+pub struct XorShift(pub u64);
+
+impl XorShift {
+    pub fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A value in `0..bound`.
+    pub fn below(&mut self, bound: usize) -> usize {
+        self.next() as usize % bound
+    }
+
+    pub fn string(&mut self, alphabet: &[char], min: usize, max: usize) -> String {
+        let len = min + self.below(max - min + 1);
+        (0..len)
+            .map(|_| alphabet[self.below(alphabet.len())])
+            .collect()
+    }
+}
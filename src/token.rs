@@ -0,0 +1,358 @@
+use std::collections::VecDeque;
+
+use crate::number::{scan_float, scan_integer};
+use crate::{Cursor, Span};
+
+/// A lexed token: its kind, where it is in the input, and its text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Token<'a, K> {
+    pub kind: K,
+    pub span: Span,
+    pub text: &'a str,
+}
+
+/// The coarse token classes of [`Cursor::tokens`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    /// Letters, digits and `_`, starting with anything but a digit.
+    Word,
+    /// An integer or float literal, such as `42`, `0xff` or `1.5e3`.
+    Number,
+    /// A single char that is neither a word char nor whitespace.
+    Punctuation,
+    /// A run of whitespace within a line.
+    Whitespace,
+    /// One line break, with `\r\n` as a single token.
+    Newline,
+}
+
+/// Iterator over classified tokens; see [`Cursor::tokens`].
+pub struct CursorTokens<'a, 'b> {
+    cursor: &'b mut Cursor<'a>,
+}
+
+impl<'a> Cursor<'a> {
+    /// Splits the remaining input into coarse tokens: words, numbers, single
+    /// punctuation chars, whitespace runs and line breaks. Every byte of the
+    /// input belongs to exactly one token, which is enough for syntax
+    /// highlighting or search indexing without writing a lexer.
+    pub fn tokens(&mut self) -> CursorTokens<'a, '_> {
+        CursorTokens { cursor: self }
+    }
+}
+
+impl<'a> Cursor<'a> {
+    /// The kind and length of the whitespace token at the cursor: a line
+    /// break, or a run of whitespace up to the next one.
+    fn space_token(&self) -> Option<(TokenKind, usize)> {
+        let (data, start) = (self.data, self.offset);
+        let rest = self.forward();
+        let breaks = |i: usize, c: char| {
+            self.terminator.ends_line(data, start + i, c) || rest[i..].starts_with("\r\n")
+        };
+        let c = rest.chars().next().filter(|c| c.is_whitespace())?;
+        if breaks(0, c) {
+            let len = if rest.starts_with("\r\n") {
+                2
+            } else {
+                c.len_utf8()
+            };
+            return Some((TokenKind::Newline, len));
+        }
+        let len = rest
+            .char_indices()
+            .find(|&(i, c)| !c.is_whitespace() || breaks(i, c))
+            .map_or(rest.len(), |(i, _)| i);
+        Some((TokenKind::Whitespace, len))
+    }
+
+    fn take_token<K>(&mut self, kind: K, len: usize) -> Token<'a, K> {
+        let start = self.offset;
+        self.advance_to(start + len);
+        Token {
+            kind,
+            span: Span::new(start, self.offset),
+            text: &self.data[start..self.offset],
+        }
+    }
+}
+
+impl<'a> Iterator for CursorTokens<'a, '_> {
+    type Item = Token<'a, TokenKind>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = self.cursor.forward();
+        let c = rest.chars().next()?;
+        let is_word = |c: char| c.is_alphanumeric() || c == '_';
+        let (kind, len) = if let Some(space) = self.cursor.space_token() {
+            space
+        } else if c.is_ascii_digit() {
+            let len = scan_float(rest).max(scan_integer(rest)).unwrap_or(1);
+            (TokenKind::Number, len)
+        } else if is_word(c) {
+            let len = rest.find(|c| !is_word(c)).unwrap_or(rest.len());
+            (TokenKind::Word, len)
+        } else {
+            (TokenKind::Punctuation, c.len_utf8())
+        };
+        Some(self.cursor.take_token(kind, len))
+    }
+}
+
+/// Iterator over the words and everything between them; see
+/// [`Cursor::words_lossless`].
+pub struct WordsLossless<'a, 'b> {
+    cursor: &'b mut Cursor<'a>,
+}
+
+impl<'a> Cursor<'a> {
+    /// Like [`Cursor::words`], but also yields the text between words, so
+    /// that concatenating the yielded texts gives back the input exactly.
+    /// Words have [`TokenKind::Word`], whatever chars they contain; between
+    /// them come [`TokenKind::Whitespace`] runs and [`TokenKind::Newline`]s,
+    /// and, with a custom [`WordSplitter`](crate::WordSplitter), runs of
+    /// skipped chars as [`TokenKind::Punctuation`].
+    ///
+    /// [`Cursor::tokens`] is lossless in the same way.
+    pub fn words_lossless(&mut self) -> WordsLossless<'a, '_> {
+        WordsLossless { cursor: self }
+    }
+}
+
+impl<'a> Iterator for WordsLossless<'a, '_> {
+    type Item = Token<'a, TokenKind>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = self.cursor.forward();
+        if rest.is_empty() {
+            return None;
+        }
+        if let Some((kind, len)) = self.cursor.space_token() {
+            return Some(self.cursor.take_token(kind, len));
+        }
+        let space = rest.find(char::is_whitespace).unwrap_or(rest.len());
+        let word = match &self.cursor.splitter {
+            Some(splitter) => splitter.0.next_word(&rest[..space]),
+            None => Some(0..space),
+        };
+        let token = match word {
+            Some(word) if word.start == 0 => (TokenKind::Word, word.end),
+            Some(word) => (TokenKind::Punctuation, word.start),
+            None => (TokenKind::Punctuation, space),
+        };
+        Some(self.cursor.take_token(token.0, token.1))
+    }
+}
+
+/// Adds k-token lookahead to any token iterator, pulling tokens lazily into a
+/// small ring buffer.
+///
+/// Buffered tokens are not reset when the source they came from is rewound,
+/// for example by [`Cursor::attempt`](crate::Cursor::attempt) inside the
+/// lexer. After rewinding the source, call [`TokenBuffer::clear`] so stale
+/// lookahead is dropped.
+#[derive(Debug, Clone)]
+pub struct TokenBuffer<'a, K, I> {
+    tokens: I,
+    buffer: VecDeque<Token<'a, K>>,
+    done: bool,
+}
+
+impl<'a, K, I: Iterator<Item = Token<'a, K>>> TokenBuffer<'a, K, I> {
+    pub fn new(tokens: I) -> Self {
+        Self {
+            tokens,
+            buffer: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    /// Pulls tokens until `n + 1` are buffered or the input ends.
+    fn fill(&mut self, n: usize) {
+        while !self.done && self.buffer.len() <= n {
+            match self.tokens.next() {
+                Some(token) => self.buffer.push_back(token),
+                None => self.done = true,
+            }
+        }
+    }
+
+    /// Returns the token `n` places ahead without consuming anything; `0` is
+    /// the token [`TokenBuffer::next_token`] would return.
+    pub fn peek_token(&mut self, n: usize) -> Option<&Token<'a, K>> {
+        self.fill(n);
+        self.buffer.get(n)
+    }
+
+    /// The span of the token `n` places ahead.
+    pub fn span_of_peek(&mut self, n: usize) -> Option<Span> {
+        self.peek_token(n).map(|token| token.span)
+    }
+
+    pub fn next_token(&mut self) -> Option<Token<'a, K>> {
+        self.fill(0);
+        self.buffer.pop_front()
+    }
+
+    /// Drops the buffered lookahead, so the next peek pulls from the
+    /// underlying iterator again.
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        self.done = false;
+    }
+
+    pub fn into_inner(self) -> I {
+        self.tokens
+    }
+}
+
+impl<'a, K, I: Iterator<Item = Token<'a, K>>> Iterator for TokenBuffer<'a, K, I> {
+    type Item = Token<'a, K>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_token()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Cursor;
+
+    fn words(input: &str) -> impl Iterator<Item = Token<'_, usize>> {
+        let mut cursor = Cursor::new(input);
+        std::iter::from_fn(move || {
+            let (start, text) = cursor.next_word()?;
+            let span = Span::new(start, start + text.len());
+            Some(Token {
+                kind: text.len(),
+                span,
+                text,
+            })
+        })
+    }
+
+    #[test]
+    fn test_tokens() {
+        use TokenKind::*;
+        let text = "let x_1 = 0x1f+2.5e3;\r\n\t// done\n";
+        let mut cursor = Cursor::new(text);
+        let tokens: Vec<_> = cursor.tokens().map(|t| (t.kind, t.text)).collect();
+        assert_eq!(
+            tokens,
+            [
+                (Word, "let"),
+                (Whitespace, " "),
+                (Word, "x_1"),
+                (Whitespace, " "),
+                (Punctuation, "="),
+                (Whitespace, " "),
+                (Number, "0x1f"),
+                (Punctuation, "+"),
+                (Number, "2.5e3"),
+                (Punctuation, ";"),
+                (Newline, "\r\n"),
+                (Whitespace, "\t"),
+                (Punctuation, "/"),
+                (Punctuation, "/"),
+                (Whitespace, " "),
+                (Word, "done"),
+                (Newline, "\n"),
+            ]
+        );
+        assert!(cursor.is_at_end());
+    }
+
+    #[test]
+    fn test_token_spans_cover_input() {
+        let text = "  3px\u{2028}é.x \r\r\n";
+        let mut cursor = Cursor::new(text).with_line_terminator(crate::LineTerminator::Unicode);
+        let (mut end, mut newlines) = (0, Vec::new());
+        for token in cursor.tokens() {
+            assert_eq!(token.span.start, end);
+            assert_eq!(&text[token.span.start..token.span.end], token.text);
+            if token.kind == TokenKind::Newline {
+                newlines.push(token.text);
+            }
+            end = token.span.end;
+        }
+        assert_eq!(end, text.len());
+        assert_eq!(newlines, ["\u{2028}", "\r", "\r\n"]);
+    }
+
+    #[test]
+    fn test_words_lossless() {
+        let text = " foo(bar,  baz)\r\n\tqux ";
+        let mut cursor = Cursor::new(text).with_word_splitter(char::is_alphanumeric);
+        let pieces: Vec<_> = cursor.words_lossless().map(|t| (t.kind, t.text)).collect();
+        use TokenKind::*;
+        assert_eq!(
+            pieces,
+            [
+                (Whitespace, " "),
+                (Word, "foo"),
+                (Punctuation, "("),
+                (Word, "bar"),
+                (Punctuation, ","),
+                (Whitespace, "  "),
+                (Word, "baz"),
+                (Punctuation, ")"),
+                (Newline, "\r\n"),
+                (Whitespace, "\t"),
+                (Word, "qux"),
+                (Whitespace, " "),
+            ]
+        );
+        let mut cursor = Cursor::new(text);
+        let words: Vec<_> = cursor.words_lossless().filter(|t| t.kind == Word).collect();
+        assert_eq!(words[1].text, "baz)");
+        assert_eq!(words[1].span, Span::new(11, 15));
+    }
+
+    #[test]
+    fn test_lossless_round_trip() {
+        let alphabet = ['a', '1', '.', ' ', '\t', '\n', '\r', '\u{2028}', 'é', '_'];
+        let mut rng = crate::test_util::XorShift(0x2545_f491_4f6c_dd1d);
+        for _ in 0..200 {
+            let text = rng.string(&alphabet, 0, 30);
+            let cursor = Cursor::new(&text).with_line_terminator(crate::LineTerminator::Unicode);
+            let tokens: String = cursor.clone().tokens().map(|t| t.text).collect();
+            assert_eq!(tokens, text);
+            let words: String = cursor.clone().words_lossless().map(|t| t.text).collect();
+            assert_eq!(words, text);
+            let mut split = cursor.with_word_splitter(char::is_alphabetic);
+            let pieces: Vec<_> = split.words_lossless().collect();
+            assert!(pieces.windows(2).all(|w| w[0].span.end == w[1].span.start));
+            assert_eq!(pieces.iter().map(|t| t.text).collect::<String>(), text);
+        }
+    }
+
+    #[test]
+    fn test_peek_ahead_then_drain() {
+        let mut tokens = TokenBuffer::new(words("let xs = [1]"));
+        assert_eq!(tokens.peek_token(2).map(|t| t.text), Some("="));
+        assert_eq!(tokens.peek_token(0).map(|t| t.text), Some("let"));
+        assert_eq!(tokens.span_of_peek(1), Some(Span::new(4, 6)));
+
+        let kinds: Vec<_> = std::iter::from_fn(|| tokens.next_token())
+            .map(|t| t.kind)
+            .collect();
+        assert_eq!(kinds, [3, 2, 1, 3]);
+    }
+
+    #[test]
+    fn test_peek_past_end_stays_none() {
+        let mut pulls = 0;
+        let inner = words("a b").inspect(|_| pulls += 1);
+        let mut tokens = TokenBuffer::new(inner);
+        assert!(tokens.peek_token(5).is_none());
+        assert!(tokens.peek_token(2).is_none());
+        assert_eq!(tokens.peek_token(1).map(|t| t.text), Some("b"));
+        assert_eq!(tokens.next_token().map(|t| t.text), Some("a"));
+        assert_eq!(tokens.next_token().map(|t| t.text), Some("b"));
+        assert!(tokens.next_token().is_none());
+        assert!(tokens.peek_token(0).is_none());
+        drop(tokens);
+        assert_eq!(pulls, 2);
+    }
+}
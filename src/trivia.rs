@@ -0,0 +1,102 @@
+use std::sync::Arc;
+
+use crate::{Cursor, LineTerminator, Span};
+
+/// The comment syntax [`Cursor::skip_trivia`] skips along with whitespace.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TriviaConfig {
+    /// Prefixes of comments that run to the end of the line, like `//`.
+    pub line_comments: Vec<String>,
+    /// Open and close delimiters of block comments, like `/*` and `*/`.
+    pub block_comments: Vec<(String, String)>,
+}
+
+impl TriviaConfig {
+    pub fn line_comment(mut self, prefix: impl Into<String>) -> Self {
+        self.line_comments.push(prefix.into());
+        self
+    }
+
+    pub fn block_comment(mut self, open: impl Into<String>, close: impl Into<String>) -> Self {
+        self.block_comments.push((open.into(), close.into()));
+        self
+    }
+
+    /// Byte length of the comment at the start of `text`, if there is one. A
+    /// line comment includes its terminator; an unterminated block comment
+    /// runs to the end.
+    fn comment_len(&self, text: &str, terminator: LineTerminator) -> Option<usize> {
+        if self
+            .line_comments
+            .iter()
+            .any(|p| text.starts_with(p.as_str()))
+        {
+            return Some(terminator.nth_after(text, 0, 0).unwrap_or(text.len()));
+        }
+        let (open, close) = self
+            .block_comments
+            .iter()
+            .find(|(open, _)| text.starts_with(open.as_str()))?;
+        let body = &text[open.len()..];
+        Some(
+            body.find(close.as_str())
+                .map_or(text.len(), |end| open.len() + end + close.len()),
+        )
+    }
+}
+
+impl<'a> Cursor<'a> {
+    /// Sets the comments that [`Cursor::skip_trivia`] skips.
+    pub fn with_trivia(mut self, config: TriviaConfig) -> Self {
+        self.trivia = Some(Arc::new(config));
+        self
+    }
+
+    /// Skips whitespace and any comments set with [`Cursor::with_trivia`],
+    /// returning the span skipped.
+    pub fn skip_trivia(&mut self) -> Span {
+        let start = self.offset;
+        loop {
+            self.skip_whitespace();
+            let len = self
+                .trivia
+                .as_ref()
+                .and_then(|trivia| trivia.comment_len(self.forward(), self.terminator));
+            match len {
+                Some(len) => self.advance_to(self.offset + len),
+                None => break,
+            }
+        }
+        Span::new(start, self.offset)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> TriviaConfig {
+        TriviaConfig::default()
+            .line_comment("//")
+            .line_comment("#")
+            .block_comment("/*", "*/")
+    }
+
+    #[test]
+    fn test_skip_trivia() {
+        let text = "  // one\n# two\n /* three\n */ x /* open";
+        let mut cursor = Cursor::new(text).with_trivia(config());
+        assert_eq!(cursor.skip_trivia(), Span::new(0, 29));
+        assert_eq!((cursor.peek_char(), cursor.line()), (Some('x'), 3));
+        assert!(cursor.skip_trivia().is_empty());
+        cursor.next();
+        cursor.skip_trivia();
+        assert!(cursor.is_at_end());
+    }
+
+    #[test]
+    fn test_without_comments_skips_whitespace() {
+        let mut cursor = Cursor::new("  // x");
+        assert_eq!(cursor.skip_trivia(), Span::new(0, 2));
+    }
+}
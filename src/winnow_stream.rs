@@ -0,0 +1,162 @@
+use std::ops::Range;
+use std::str::CharIndices;
+
+use winnow::error::Needed;
+use winnow::stream::{
+    Compare, CompareResult, FindSlice, Location, Offset, Stream, StreamIsPartial,
+};
+
+use crate::{Checkpoint, Cursor};
+
+/// The stream is what remains after the cursor, and consuming it moves the
+/// cursor, so a parser can ask for [`Cursor::position`] at any point and
+/// error offsets line up with the cursor's.
+impl<'a> Stream for Cursor<'a> {
+    type Token = char;
+    type Slice = &'a str;
+    type IterOffsets = CharIndices<'a>;
+    type Checkpoint = Checkpoint;
+
+    fn iter_offsets(&self) -> Self::IterOffsets {
+        self.forward().char_indices()
+    }
+
+    fn eof_offset(&self) -> usize {
+        self.forward().len()
+    }
+
+    fn next_token(&mut self) -> Option<Self::Token> {
+        Iterator::next(self).map(|(_, c)| c)
+    }
+
+    fn peek_token(&self) -> Option<Self::Token> {
+        self.peek_char()
+    }
+
+    fn offset_for<P>(&self, predicate: P) -> Option<usize>
+    where
+        P: Fn(Self::Token) -> bool,
+    {
+        self.forward().find(predicate)
+    }
+
+    fn offset_at(&self, tokens: usize) -> Result<usize, Needed> {
+        self.forward().offset_at(tokens)
+    }
+
+    fn next_slice(&mut self, offset: usize) -> Self::Slice {
+        let slice = &self.forward()[..offset];
+        self.advance_to(self.offset + offset);
+        slice
+    }
+
+    fn peek_slice(&self, offset: usize) -> Self::Slice {
+        &self.forward()[..offset]
+    }
+
+    fn checkpoint(&self) -> Self::Checkpoint {
+        Cursor::checkpoint(self)
+    }
+
+    fn reset(&mut self, checkpoint: &Self::Checkpoint) {
+        self.restore(*checkpoint);
+    }
+
+    fn trace(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:#?}", self.forward())
+    }
+}
+
+impl Location for Cursor<'_> {
+    fn previous_token_end(&self) -> usize {
+        self.offset
+    }
+
+    fn current_token_start(&self) -> usize {
+        self.offset
+    }
+}
+
+impl StreamIsPartial for Cursor<'_> {
+    type PartialState = ();
+
+    fn complete(&mut self) -> Self::PartialState {}
+
+    fn restore_partial(&mut self, _state: Self::PartialState) {}
+
+    fn is_partial_supported() -> bool {
+        false
+    }
+}
+
+impl Offset for Cursor<'_> {
+    fn offset_from(&self, start: &Self) -> usize {
+        self.offset - start.offset
+    }
+}
+
+impl Offset<Checkpoint> for Cursor<'_> {
+    fn offset_from(&self, start: &Checkpoint) -> usize {
+        self.offset - start.offset()
+    }
+}
+
+impl Offset for Checkpoint {
+    fn offset_from(&self, start: &Self) -> usize {
+        self.offset() - start.offset()
+    }
+}
+
+impl<T> Compare<T> for Cursor<'_>
+where
+    for<'s> &'s str: Compare<T>,
+{
+    fn compare(&self, t: T) -> CompareResult {
+        self.forward().compare(t)
+    }
+}
+
+impl<T> FindSlice<T> for Cursor<'_>
+where
+    for<'s> &'s str: FindSlice<T>,
+{
+    fn find_slice(&self, substr: T) -> Option<Range<usize>> {
+        self.forward().find_slice(substr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use winnow::ascii::{alpha1, digit1, multispace0};
+    use winnow::combinator::{delimited, separated_pair};
+    use winnow::prelude::*;
+    use winnow::token::take_until;
+
+    use super::*;
+
+    fn assignment(input: &mut Cursor<'_>) -> ModalResult<(Range<usize>, u32)> {
+        let name = delimited(multispace0, alpha1.span(), multispace0);
+        let value = delimited(multispace0, digit1.parse_to(), multispace0);
+        separated_pair(name, '=', value).parse_next(input)
+    }
+
+    #[test]
+    fn test_parse_cursor() {
+        let mut input = Cursor::new("\n  width = 42;\n-- note --");
+        let (name, value) = assignment(&mut input).unwrap();
+        assert_eq!((name, value), (3..8, 42));
+        assert_eq!(input.peek_char(), Some(';'));
+        let mut note = delimited(";\n--", take_until(0.., "--"), "--");
+        let note: ModalResult<&str> = note.parse_next(&mut input);
+        assert_eq!(note, Ok(" note "));
+        assert!(input.is_at_end());
+    }
+
+    #[test]
+    fn test_error_position() {
+        let err = assignment.parse(Cursor::new("a =\n  b")).unwrap_err();
+        let mut at = err.input().clone();
+        at.seek_to_offset(err.offset()).unwrap();
+        assert_eq!(at.position().to_string(), "2:3");
+    }
+}
@@ -0,0 +1,268 @@
+use std::fmt;
+use std::ops::Range;
+use std::sync::Arc;
+
+use crate::Cursor;
+
+/// Decides what counts as a word for [`Cursor::next_word`], [`Cursor::words`]
+/// and the other word methods; set with [`Cursor::with_word_splitter`].
+///
+/// Any `Fn(char) -> bool` is a splitter that treats runs of the chars it
+/// accepts as words, so `char::is_alphanumeric` splits on punctuation too.
+pub trait WordSplitter {
+    /// The byte range of the first word in `text`, if any.
+    fn next_word(&self, text: &str) -> Option<Range<usize>>;
+
+    /// The byte range of the last word in `text`, if any.
+    fn prev_word(&self, text: &str) -> Option<Range<usize>>;
+}
+
+impl<F: Fn(char) -> bool> WordSplitter for F {
+    fn next_word(&self, text: &str) -> Option<Range<usize>> {
+        let start = text.find(self)?;
+        let len = text[start..].find(|c| !self(c));
+        Some(start..len.map_or(text.len(), |len| start + len))
+    }
+
+    fn prev_word(&self, text: &str) -> Option<Range<usize>> {
+        let end = text.trim_end_matches(|c| !self(c)).len();
+        let start = text[..end].trim_end_matches(self).len();
+        (start < end).then_some(start..end)
+    }
+}
+
+/// Splits words at the Unicode word boundaries of [UAX #29], keeping the
+/// segments that contain a letter or digit. Unlike whitespace splitting, it
+/// separates punctuation from words and handles scripts written without
+/// spaces.
+///
+/// [UAX #29]: https://www.unicode.org/reports/tr29/
+#[cfg(feature = "segmentation")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UnicodeWords;
+
+#[cfg(feature = "segmentation")]
+impl WordSplitter for UnicodeWords {
+    fn next_word(&self, text: &str) -> Option<Range<usize>> {
+        use unicode_segmentation::UnicodeSegmentation;
+        let (start, word) = text.unicode_word_indices().next()?;
+        Some(start..start + word.len())
+    }
+
+    fn prev_word(&self, text: &str) -> Option<Range<usize>> {
+        use unicode_segmentation::UnicodeSegmentation;
+        let (start, word) = text.unicode_word_indices().next_back()?;
+        Some(start..start + word.len())
+    }
+}
+
+/// A shared splitter, so that cursors stay cheap to clone.
+#[derive(Clone)]
+pub(crate) struct SharedSplitter(pub(crate) Arc<dyn WordSplitter + Send + Sync>);
+
+impl fmt::Debug for SharedSplitter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("WordSplitter")
+    }
+}
+
+/// Byte length of the subword at the start of `text`, which must start with
+/// a letter or digit: a run of digits, a capitalized or lowercase run of
+/// letters, or an acronym, which gives up its last capital when a lowercase
+/// letter follows, as in `HTTPRequest`.
+fn subword_len(text: &str) -> usize {
+    let run = |pred: fn(char) -> bool, from: usize| {
+        text[from..]
+            .find(|c| !pred(c))
+            .map_or(text.len(), |len| from + len)
+    };
+    let is_lower = |c: char| c.is_alphabetic() && !c.is_uppercase();
+    let first = text.chars().next().map_or(0, char::len_utf8);
+    match text.chars().next() {
+        Some(c) if c.is_numeric() => run(char::is_numeric, 0),
+        Some(c) if c.is_uppercase() => {
+            let caps = run(char::is_uppercase, 0);
+            if caps == first {
+                return run(is_lower, first);
+            }
+            match text[caps..].chars().next() {
+                Some(c) if is_lower(c) => text[..caps]
+                    .char_indices()
+                    .next_back()
+                    .map_or(0, |(i, _)| i),
+                _ => caps,
+            }
+        }
+        Some(_) => run(is_lower, 0),
+        None => 0,
+    }
+}
+
+impl<'a> Cursor<'a> {
+    /// Skips to the next letter or digit and consumes the subword there,
+    /// stopping at case changes, digits and underscores, so that
+    /// `parseHTTPRequest_v2` splits into `parse`, `HTTP`, `Request`, `v` and
+    /// `2`.
+    pub fn next_subword(&mut self) -> Option<(usize, &'a str)> {
+        let skip = self.forward().find(char::is_alphanumeric)?;
+        let start = self.offset + skip;
+        self.advance_to(start + subword_len(&self.data[start..]));
+        Some((start, &self.data[start..self.offset]))
+    }
+
+    /// Moves back to the start of the subword before the cursor, skipping
+    /// anything that isn't a letter or digit; the mirror of
+    /// [`Cursor::next_subword`].
+    pub fn prev_subword(&mut self) -> Option<(usize, &'a str)> {
+        let before = self.backward();
+        let end = before
+            .trim_end_matches(|c: char| !c.is_alphanumeric())
+            .len();
+        if end == 0 {
+            return None;
+        }
+        let run = before[..end].trim_end_matches(char::is_alphanumeric).len();
+        let mut start = run;
+        while start + subword_len(&before[start..end]) < end {
+            start += subword_len(&before[start..end]);
+        }
+        self.retreat_to(start);
+        Some((start, &self.data[start..end]))
+    }
+
+    /// Uses `splitter` instead of whitespace to find words.
+    pub fn with_word_splitter(
+        mut self,
+        splitter: impl WordSplitter + Send + Sync + 'static,
+    ) -> Self {
+        self.splitter = Some(SharedSplitter(Arc::new(splitter)));
+        self
+    }
+
+    /// [`Cursor::next_word`] with a custom splitter. Without a word ahead the
+    /// cursor moves to the end, as it does past trailing whitespace.
+    pub(crate) fn split_next_word(
+        &mut self,
+        splitter: &dyn WordSplitter,
+    ) -> Option<(usize, &'a str)> {
+        let Some(range) = splitter.next_word(self.forward()) else {
+            self.advance_to(self.data.len());
+            return None;
+        };
+        let start = self.offset + range.start;
+        self.advance_to(self.offset + range.end);
+        Some((start, &self.data[start..self.offset]))
+    }
+
+    pub(crate) fn split_prev_word(
+        &mut self,
+        splitter: &dyn WordSplitter,
+    ) -> Option<(usize, &'a str)> {
+        let Some(range) = splitter.prev_word(self.backward()) else {
+            self.retreat_to(0);
+            return None;
+        };
+        let end = range.end;
+        self.retreat_to(range.start);
+        Some((range.start, &self.data[range.start..end]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_ident(c: char) -> bool {
+        c.is_alphanumeric() || c == '_'
+    }
+
+    #[test]
+    fn test_custom_splitter() {
+        let mut cursor = Cursor::new("foo.bar(x_1, -y)").with_word_splitter(is_ident);
+        let words: Vec<_> = cursor.words().collect();
+        assert_eq!(words, [(0, "foo"), (4, "bar"), (8, "x_1"), (14, "y")]);
+        assert_eq!(cursor.next_word(), None);
+        assert!(cursor.is_at_end());
+
+        let back: Vec<_> = cursor.words_rev().map(|(_, w)| w).collect();
+        assert_eq!(back, ["y", "x_1", "bar", "foo"]);
+        assert!(cursor.is_at_start());
+    }
+
+    #[test]
+    fn test_remainder_with_splitter() {
+        let mut cursor = Cursor::new("foo.bar, baz!").with_word_splitter(char::is_alphanumeric);
+        let mut words = cursor.words();
+        assert_eq!(words.next(), Some((0, "foo")));
+        assert_eq!(words.remainder(), Some("bar, baz!"));
+        assert_eq!(words.next(), Some((4, "bar")));
+        words.next();
+        assert_eq!(words.remainder(), None);
+    }
+
+    #[test]
+    fn test_splitter_survives_clone() {
+        let cursor = Cursor::new("a-b c").with_word_splitter(char::is_alphanumeric);
+        assert_eq!(cursor.peek_nth_word(1), Some((2, "b")));
+        let ahead: Vec<_> = cursor.peek_words().map(|(_, w)| w).collect();
+        assert_eq!(ahead, ["a", "b", "c"]);
+    }
+
+    #[cfg(feature = "segmentation")]
+    #[test]
+    fn test_unicode_words() {
+        let text = "The quick (\"brown\") fox can't jump 32.3 feet, right?";
+        let mut cursor = Cursor::new(text).with_word_splitter(UnicodeWords);
+        let words: Vec<_> = cursor.words().map(|(_, w)| w).collect();
+        assert_eq!(
+            words,
+            ["The", "quick", "brown", "fox", "can't", "jump", "32.3", "feet", "right"]
+        );
+        assert_eq!(cursor.prev_word(), Some((46, "right")));
+
+        let mut cursor = Cursor::new("東京は晴れ").with_word_splitter(UnicodeWords);
+        assert!(cursor.words().count() > 1);
+    }
+
+    #[test]
+    fn test_subwords() {
+        let text = "let parseHTTPRequest_v2 = XMLParser;";
+        let mut cursor = Cursor::new(text);
+        let mut forward = Vec::new();
+        while let Some((_, sub)) = cursor.next_subword() {
+            forward.push(sub);
+        }
+        assert_eq!(
+            forward,
+            ["let", "parse", "HTTP", "Request", "v", "2", "XML", "Parser"]
+        );
+        assert_eq!(cursor.offset(), text.len() - 1);
+
+        let mut back = Vec::new();
+        while let Some((start, sub)) = cursor.prev_subword() {
+            assert_eq!(cursor.offset(), start);
+            back.push(sub);
+        }
+        forward.reverse();
+        assert_eq!(back, forward);
+        assert_eq!(Cursor::new("_ - _").next_subword(), None);
+    }
+
+    #[test]
+    fn test_subword_from_inside_a_word() {
+        let mut cursor = Cursor::new("fooBarBaz");
+        cursor.advance_by(5);
+        assert_eq!(cursor.prev_subword(), Some((3, "Ba")));
+        assert_eq!(cursor.next_subword(), Some((3, "Bar")));
+        assert_eq!(cursor.next_subword(), Some((6, "Baz")));
+    }
+
+    #[test]
+    fn test_closure_ranges() {
+        let digits = |c: char| c.is_ascii_digit();
+        assert_eq!(digits.next_word("ab12c3"), Some(2..4));
+        assert_eq!(digits.prev_word("ab12c3x"), Some(5..6));
+        assert_eq!(digits.next_word("abc"), None);
+        assert_eq!(digits.prev_word(""), None);
+    }
+}